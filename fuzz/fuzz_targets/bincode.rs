@@ -0,0 +1,42 @@
+#![no_main]
+
+// deserializing the serde binary format from arbitrary bytes must not
+// panic; successfully deserialized values must round-trip
+
+use cidr::{
+	AnyIpCidr,
+	AnyIpInet,
+	IpCidr,
+	IpInet,
+	Ipv4Cidr,
+	Ipv4Inet,
+	Ipv6Cidr,
+	Ipv6Inet,
+};
+use libfuzzer_sys::fuzz_target;
+use serde::{
+	de::DeserializeOwned,
+	Serialize,
+};
+
+fn check<T>(data: &[u8])
+where
+	T: DeserializeOwned + Serialize + PartialEq + core::fmt::Debug,
+{
+	if let Ok(value) = bincode::deserialize::<T>(data) {
+		let raw = bincode::serialize(&value).unwrap();
+		assert_eq!(&data[..raw.len()], &raw[..]);
+		assert_eq!(bincode::deserialize::<T>(&raw).unwrap(), value);
+	}
+}
+
+fuzz_target!(|data: &[u8]| {
+	check::<Ipv4Cidr>(data);
+	check::<Ipv6Cidr>(data);
+	check::<IpCidr>(data);
+	check::<AnyIpCidr>(data);
+	check::<Ipv4Inet>(data);
+	check::<Ipv6Inet>(data);
+	check::<IpInet>(data);
+	check::<AnyIpInet>(data);
+});
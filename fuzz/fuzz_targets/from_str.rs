@@ -0,0 +1,61 @@
+#![no_main]
+
+// `FromStr` on every type must not panic, agree between the types, and
+// agree with the const parsers
+
+use cidr::{
+	AnyIpCidr,
+	AnyIpInet,
+	IpCidr,
+	IpInet,
+	Ipv4Cidr,
+	Ipv4Inet,
+	Ipv6Cidr,
+	Ipv6Inet,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|s: &str| {
+	let v4_cidr = s.parse::<Ipv4Cidr>();
+	let v6_cidr = s.parse::<Ipv6Cidr>();
+	let ip_cidr = s.parse::<IpCidr>();
+	let any_cidr = s.parse::<AnyIpCidr>();
+	let v4_inet = s.parse::<Ipv4Inet>();
+	let v6_inet = s.parse::<Ipv6Inet>();
+	let ip_inet = s.parse::<IpInet>();
+	let any_inet = s.parse::<AnyIpInet>();
+
+	assert_eq!(v4_cidr.clone().ok(), Ipv4Cidr::parse_const(s).ok());
+	assert_eq!(v6_cidr.clone().ok(), Ipv6Cidr::parse_const(s).ok());
+	assert_eq!(ip_cidr.clone().ok(), IpCidr::parse_const(s).ok());
+	assert_eq!(any_cidr.clone().ok(), AnyIpCidr::parse_const(s).ok());
+	assert_eq!(v4_inet.clone().ok(), Ipv4Inet::parse_const(s).ok());
+	assert_eq!(v6_inet.clone().ok(), Ipv6Inet::parse_const(s).ok());
+	assert_eq!(ip_inet.clone().ok(), IpInet::parse_const(s).ok());
+	assert_eq!(any_inet.clone().ok(), AnyIpInet::parse_const(s).ok());
+
+	let expected_ip_cidr = match (v4_cidr, v6_cidr) {
+		(Ok(c), _) => Some(IpCidr::V4(c)),
+		(_, Ok(c)) => Some(IpCidr::V6(c)),
+		_ => None,
+	};
+	assert_eq!(ip_cidr.clone().ok(), expected_ip_cidr);
+	if let Ok(c) = ip_cidr {
+		assert_eq!(any_cidr, Ok(c.into()));
+	}
+
+	let expected_ip_inet = match (v4_inet, v6_inet) {
+		(Ok(c), _) => Some(IpInet::V4(c)),
+		(_, Ok(c)) => Some(IpInet::V6(c)),
+		_ => None,
+	};
+	assert_eq!(ip_inet.clone().ok(), expected_ip_inet);
+	if let Ok(c) = ip_inet {
+		assert_eq!(any_inet, Ok(c.into()));
+	}
+
+	// a valid network is also a valid host (the first one)
+	if let Ok(c) = ip_cidr {
+		assert_eq!(ip_inet, Ok(c.first()));
+	}
+});
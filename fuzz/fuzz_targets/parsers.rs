@@ -0,0 +1,75 @@
+#![no_main]
+
+// the `parsers` module: loose and short address parsers and the parser
+// combinators must not panic and must be consistent with each other and
+// with the strict `FromStr` implementations
+
+use core::net::IpAddr;
+
+use cidr::{
+	parsers,
+	AnyIpCidr,
+	IpCidr,
+	IpInet,
+	Ipv4Cidr,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|s: &str| {
+	// loose address parsers
+	let loose_v4 = parsers::parse_loose_ipv4(s);
+	let loose_ip = parsers::parse_loose_ip(s);
+	assert_eq!(parsers::inet_addr(s), loose_v4.clone().ok());
+	if let Ok(a) = loose_v4 {
+		assert_eq!(loose_ip, Ok(IpAddr::V4(a)));
+	}
+	if let Ok(a) = s.parse::<IpAddr>() {
+		assert_eq!(loose_ip, Ok(a));
+	}
+
+	// short IPv4 forms
+	let short_v4 = parsers::parse_short_ipv4_address_as_cidr(s);
+	let short_ip = parsers::parse_short_ip_address_as_cidr(s);
+	if let Ok(c) = short_v4 {
+		assert_eq!(short_ip, Ok(IpCidr::V4(c)));
+	}
+	let short_v4_cidr = parsers::parse_short_ipv4_cidr(s);
+	let short_ip_cidr = parsers::parse_short_ip_cidr(s);
+	let short_any_cidr = parsers::parse_short_any_ip_cidr(s);
+	if let Ok(c) = short_v4_cidr {
+		assert_eq!(short_ip_cidr, Ok(IpCidr::V4(c)));
+	}
+	if let Ok(c) = short_ip_cidr {
+		assert_eq!(short_any_cidr, Ok(c.into()));
+	}
+	if let Ok(c) = s.parse::<Ipv4Cidr>() {
+		assert_eq!(short_v4_cidr, Ok(c));
+	}
+	if let Ok(c) = s.parse::<IpCidr>() {
+		assert_eq!(short_ip_cidr, Ok(c));
+	}
+
+	// combinators with the loose address parser
+	let cidr = parsers::parse_cidr::<IpCidr, _>(s, parsers::parse_loose_ip);
+	let cidr_ignore = parsers::parse_cidr_ignore_hostbits::<IpCidr, _>(s, parsers::parse_loose_ip);
+	let any_cidr = parsers::parse_any_cidr(s, parsers::parse_loose_ip);
+	let any_cidr_ignore = parsers::parse_any_cidr_ignore_hostbits(s, parsers::parse_loose_ip);
+	let inet = parsers::parse_inet::<IpInet, _>(s, parsers::parse_loose_ip);
+	if let Ok(c) = cidr {
+		assert_eq!(cidr_ignore, Ok(c));
+		assert_eq!(any_cidr, Ok(c.into()));
+		assert_eq!(inet, Ok(c.first()));
+	}
+	if let Ok(c) = cidr_ignore {
+		assert_eq!(any_cidr_ignore, Ok(c.into()));
+	}
+	if let Ok(i) = inet {
+		assert_eq!(cidr_ignore, Ok(i.network()));
+	}
+	if any_cidr_ignore == Ok(AnyIpCidr::Any) {
+		assert_eq!(s, "any");
+	}
+	if let Ok(c) = s.parse::<IpCidr>() {
+		assert_eq!(cidr, Ok(c));
+	}
+});
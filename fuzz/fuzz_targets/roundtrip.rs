@@ -0,0 +1,57 @@
+#![no_main]
+
+// parse -> display -> parse must return the same value (also with the
+// alternate `{:#}` format, which always shows the network length)
+
+use core::{
+	fmt::Display,
+	str::FromStr,
+};
+
+use cidr::{
+	AnyIpCidr,
+	AnyIpInet,
+	IpCidr,
+	IpInet,
+	Ipv4Cidr,
+	Ipv4Inet,
+	Ipv6Cidr,
+	Ipv6Inet,
+};
+use libfuzzer_sys::fuzz_target;
+
+fn check<T>(s: &str)
+where
+	T: FromStr + Display + PartialEq + core::fmt::Debug,
+	T::Err: core::fmt::Debug,
+{
+	if let Ok(value) = s.parse::<T>() {
+		let display = value.to_string();
+		assert_eq!(
+			display.parse::<T>().unwrap(),
+			value,
+			"{:?} -> {:?}",
+			s,
+			display
+		);
+		let alternate = format!("{:#}", value);
+		assert_eq!(
+			alternate.parse::<T>().unwrap(),
+			value,
+			"{:?} -> {:?}",
+			s,
+			alternate
+		);
+	}
+}
+
+fuzz_target!(|s: &str| {
+	check::<Ipv4Cidr>(s);
+	check::<Ipv6Cidr>(s);
+	check::<IpCidr>(s);
+	check::<AnyIpCidr>(s);
+	check::<Ipv4Inet>(s);
+	check::<Ipv6Inet>(s);
+	check::<IpInet>(s);
+	check::<AnyIpInet>(s);
+});
@@ -0,0 +1,291 @@
+//! Anonymization of IP addresses (e.g. for logs)
+//!
+//! [`Truncation`] zeroes the host part of addresses beyond a configurable
+//! network length per family (e.g. /24 for IPv4 and /48 for IPv6).
+//!
+//! [`CryptoPan`] is a keyed, prefix-preserving mapping (Crypto-PAn, Xu et
+//! al.): if two addresses share a prefix of `k` bits, the mapped
+//! addresses share a prefix of `k` bits too, so the subnet structure is
+//! preserved.  It is built on a 128-bit block cipher provided through
+//! the [`BlockCipher`] trait; with AES-128 the results match the
+//! original Crypto-PAn implementation for IPv4 addresses.
+//!
+//! ```
+//! use cidr::anonymize::{Anonymizer, Truncation};
+//! use std::net::IpAddr;
+//!
+//! let truncation = Truncation::default();
+//! let addr: IpAddr = "2001:db8:1:2::1".parse().unwrap();
+//! assert_eq!(truncation.anonymize(addr), "2001:db8:1::".parse::<IpAddr>().unwrap());
+//! ```
+
+use core::net::{
+	IpAddr,
+	Ipv4Addr,
+	Ipv6Addr,
+};
+
+use crate::{
+	errors::NetworkLengthTooLongError,
+	Family,
+	IpCidr,
+	IpInet,
+};
+
+/// Mapping of addresses to anonymized addresses
+pub trait Anonymizer {
+	/// Anonymize address
+	fn anonymize(&self, addr: IpAddr) -> IpAddr;
+
+	/// Anonymize the address of a host, keeping the network length
+	fn anonymize_inet(&self, inet: IpInet) -> IpInet {
+		IpInet::new(self.anonymize(inet.address()), inet.network_length())
+			.expect("same family and network length")
+	}
+}
+
+/// Anonymize addresses by truncating them to a network length (i.e. set
+/// the host part to zero)
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Truncation {
+	ipv4_length: u8,
+	ipv6_length: u8,
+}
+
+impl Truncation {
+	/// Truncate IPv4 addresses to `ipv4_length` and IPv6 addresses to
+	/// `ipv6_length` bits
+	pub const fn new(ipv4_length: u8, ipv6_length: u8) -> Result<Self, NetworkLengthTooLongError> {
+		if ipv4_length > 32 {
+			return Err(NetworkLengthTooLongError::new(
+				ipv4_length as usize,
+				Family::Ipv4,
+			));
+		}
+		if ipv6_length > 128 {
+			return Err(NetworkLengthTooLongError::new(
+				ipv6_length as usize,
+				Family::Ipv6,
+			));
+		}
+		Ok(Self {
+			ipv4_length,
+			ipv6_length,
+		})
+	}
+
+	/// number of bits kept in IPv4 addresses
+	pub const fn ipv4_length(&self) -> u8 {
+		self.ipv4_length
+	}
+
+	/// number of bits kept in IPv6 addresses
+	pub const fn ipv6_length(&self) -> u8 {
+		self.ipv6_length
+	}
+
+	/// number of bits kept in addresses of the given family
+	pub const fn network_length(&self, family: Family) -> u8 {
+		match family {
+			Family::Ipv4 => self.ipv4_length,
+			Family::Ipv6 => self.ipv6_length,
+		}
+	}
+
+	/// Network the address is truncated to
+	pub const fn network(&self, addr: IpAddr) -> IpCidr {
+		match IpInet::new(addr, self.network_length(Family::of(&addr))) {
+			Ok(inet) => inet.network(),
+			Err(_) => panic!("valid network length"),
+		}
+	}
+
+	/// Truncate address
+	pub const fn truncate(&self, addr: IpAddr) -> IpAddr {
+		self.network(addr).first_address()
+	}
+}
+
+/// Truncates IPv4 addresses to /24 and IPv6 addresses to /48
+impl Default for Truncation {
+	fn default() -> Self {
+		Self {
+			ipv4_length: 24,
+			ipv6_length: 48,
+		}
+	}
+}
+
+impl Anonymizer for Truncation {
+	fn anonymize(&self, addr: IpAddr) -> IpAddr {
+		self.truncate(addr)
+	}
+}
+
+/// 128-bit block cipher (or pseudo-random permutation) used by
+/// [`CryptoPan`], e.g. AES-128
+pub trait BlockCipher {
+	/// Encrypt a single block
+	fn encrypt_block(&self, block: [u8; 16]) -> [u8; 16];
+}
+
+/// Keyed prefix-preserving anonymization (Crypto-PAn)
+///
+/// Bit `i` of an address is flipped depending on the first bit of the
+/// encrypted first `i` bits of the address (padded with a secret pad),
+/// so the mapping is a permutation of the addresses of each family and
+/// preserves shared prefixes.  Needs one block encryption per address
+/// bit.
+///
+/// The original Crypto-PAn uses a 32-byte key: the first 16 bytes as
+/// AES-128 key for the `cipher` and the remaining 16 bytes as
+/// `pad_seed`.
+#[derive(Clone, Debug)]
+pub struct CryptoPan<C> {
+	cipher: C,
+	pad: u128,
+}
+
+impl<C: BlockCipher> CryptoPan<C> {
+	/// Create new anonymizer; the pad is the encrypted `pad_seed`
+	pub fn new(cipher: C, pad_seed: [u8; 16]) -> Self {
+		let pad = u128::from_be_bytes(cipher.encrypt_block(pad_seed));
+		Self { cipher, pad }
+	}
+
+	/// Anonymize IPv4 address
+	pub fn anonymize_ipv4(&self, addr: Ipv4Addr) -> Ipv4Addr {
+		let bits = (u32::from(addr) as u128) << 96;
+		Ipv4Addr::from((self.map(bits, 32) >> 96) as u32)
+	}
+
+	/// Anonymize IPv6 address
+	pub fn anonymize_ipv6(&self, addr: Ipv6Addr) -> Ipv6Addr {
+		Ipv6Addr::from(self.map(u128::from(addr), 128))
+	}
+
+	// map the first `len` bits of `addr`; shorter addresses are aligned to
+	// the most significant bits, and the remaining bits are taken from
+	// the pad (as in the original IPv4 implementation)
+	fn map(&self, addr: u128, len: u32) -> u128 {
+		let mut flip = 0u128;
+		for pos in 0..len {
+			let prefix = u128::MAX.checked_shl(128 - pos).unwrap_or(0);
+			let input = (addr & prefix) | (self.pad & !prefix);
+			let output = self.cipher.encrypt_block(input.to_be_bytes());
+			flip |= ((output[0] >> 7) as u128) << (127 - pos);
+		}
+		addr ^ flip
+	}
+}
+
+impl<C: BlockCipher> Anonymizer for CryptoPan<C> {
+	fn anonymize(&self, addr: IpAddr) -> IpAddr {
+		match addr {
+			IpAddr::V4(a) => IpAddr::V4(self.anonymize_ipv4(a)),
+			IpAddr::V6(a) => IpAddr::V6(self.anonymize_ipv6(a)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use aes::cipher::{
+		BlockEncrypt,
+		KeyInit,
+	};
+
+	use super::*;
+
+	struct Aes(aes::Aes128);
+
+	impl BlockCipher for Aes {
+		fn encrypt_block(&self, block: [u8; 16]) -> [u8; 16] {
+			let mut block = block.into();
+			self.0.encrypt_block(&mut block);
+			block.into()
+		}
+	}
+
+	// key from the Crypto-PAn reference implementation sample
+	const KEY: [u8; 32] = [
+		21, 34, 23, 141, 51, 164, 207, 128, 19, 10, 91, 22, 73, 144, 125, 16, 216, 152, 143, 131,
+		121, 121, 101, 39, 98, 87, 76, 45, 42, 132, 34, 2,
+	];
+
+	fn crypto_pan() -> CryptoPan<Aes> {
+		let cipher = Aes(aes::Aes128::new_from_slice(&KEY[..16]).unwrap());
+		CryptoPan::new(cipher, KEY[16..].try_into().unwrap())
+	}
+
+	#[test]
+	fn truncation() {
+		let t = Truncation::new(16, 32).unwrap();
+		assert_eq!(
+			t.anonymize("192.0.2.1".parse().unwrap()),
+			"192.0.0.0".parse::<IpAddr>().unwrap()
+		);
+		assert_eq!(
+			t.anonymize_inet("2001:db8:1::1/64".parse().unwrap()),
+			"2001:db8::/64".parse().unwrap()
+		);
+		assert_eq!(
+			Truncation::default().anonymize("192.0.2.1".parse().unwrap()),
+			"192.0.2.0".parse::<IpAddr>().unwrap()
+		);
+		assert!(Truncation::new(33, 48).is_err());
+		assert!(Truncation::new(24, 129).is_err());
+	}
+
+	#[test]
+	fn crypto_pan_reference() {
+		let pan = crypto_pan();
+		for (addr, anonymized) in [
+			("128.11.68.132", "135.242.180.132"),
+			("129.118.74.4", "134.136.186.123"),
+			("130.132.252.244", "133.68.164.234"),
+			("141.223.7.43", "141.167.8.160"),
+			("141.233.145.108", "141.129.237.235"),
+			("192.102.249.13", "252.138.62.131"),
+		] {
+			assert_eq!(
+				pan.anonymize_ipv4(addr.parse().unwrap()),
+				anonymized.parse::<Ipv4Addr>().unwrap(),
+				"{}",
+				addr
+			);
+		}
+	}
+
+	#[test]
+	fn crypto_pan_prefix_preserving() {
+		let pan = crypto_pan();
+		let addrs: [IpAddr; 6] = [
+			"2001:db8::1".parse().unwrap(),
+			"2001:db8::2".parse().unwrap(),
+			"2001:db8:1::1".parse().unwrap(),
+			"2001:db9::1".parse().unwrap(),
+			"10.0.0.1".parse().unwrap(),
+			"10.0.1.1".parse().unwrap(),
+		];
+		let common_prefix = |a: IpAddr, b: IpAddr| match (a, b) {
+			(IpAddr::V4(a), IpAddr::V4(b)) => Some((u32::from(a) ^ u32::from(b)).leading_zeros()),
+			(IpAddr::V6(a), IpAddr::V6(b)) => Some((u128::from(a) ^ u128::from(b)).leading_zeros()),
+			_ => None,
+		};
+		for a in addrs {
+			for b in addrs {
+				assert_eq!(
+					common_prefix(pan.anonymize(a), pan.anonymize(b)),
+					common_prefix(a, b),
+					"{} {}",
+					a,
+					b
+				);
+			}
+		}
+		let inet = pan.anonymize_inet("2001:db8::1/64".parse().unwrap());
+		assert_eq!(inet.network_length(), 64);
+		assert_eq!(inet.address(), pan.anonymize(addrs[0]));
+	}
+}
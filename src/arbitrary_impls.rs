@@ -0,0 +1,194 @@
+#![cfg(feature = "arbitrary")]
+
+// `arbitrary::Arbitrary` implementations (e.g. for cargo-fuzz)
+//
+// All generated values are valid by construction: networks have a zero
+// host part and both addresses of a pair are in the same network.
+
+use core::net::{
+	Ipv4Addr,
+	Ipv6Addr,
+};
+
+use arbitrary::{
+	size_hint,
+	Arbitrary,
+	Result,
+	Unstructured,
+};
+
+use crate::{
+	AnyIpCidr,
+	AnyIpInet,
+	Family,
+	IpCidr,
+	IpInet,
+	IpInetPair,
+	Ipv4Cidr,
+	Ipv4Inet,
+	Ipv4InetPair,
+	Ipv6Cidr,
+	Ipv6Inet,
+	Ipv6InetPair,
+};
+
+macro_rules! impl_arbitrary_for {
+	($cidr:ident : inet $inet:ident : pair $pair:ident : addr $addr:ident : native $native:ident : family $family:expr) => {
+		impl<'a> Arbitrary<'a> for $inet {
+			fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+				let address = $addr::arbitrary(u)?;
+				let len = u.int_in_range(0..=$family.len())?;
+				Ok($inet::new(address, len).expect("valid network length"))
+			}
+
+			fn size_hint(depth: usize) -> (usize, Option<usize>) {
+				size_hint::and($addr::size_hint(depth), (0, Some(1)))
+			}
+		}
+
+		impl<'a> Arbitrary<'a> for $cidr {
+			fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+				Ok($inet::arbitrary(u)?.network())
+			}
+
+			fn size_hint(depth: usize) -> (usize, Option<usize>) {
+				$inet::size_hint(depth)
+			}
+		}
+
+		impl<'a> Arbitrary<'a> for $pair {
+			fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+				let first = $inet::arbitrary(u)?;
+				// network part from `first`, host part from `other`
+				let other = $native::from($addr::arbitrary(u)?);
+				let mask = $native::from(first.mask());
+				let second = $addr::from(($native::from(first.address) & mask) | (other & !mask));
+				Ok(
+					$pair::new_from_addresses(first.address, second, first.network_length)
+						.expect("same network"),
+				)
+			}
+
+			fn size_hint(depth: usize) -> (usize, Option<usize>) {
+				size_hint::and($inet::size_hint(depth), $addr::size_hint(depth))
+			}
+		}
+	};
+}
+
+impl_arbitrary_for! {Ipv4Cidr : inet Ipv4Inet : pair Ipv4InetPair : addr Ipv4Addr : native u32 : family Family::Ipv4}
+impl_arbitrary_for! {Ipv6Cidr : inet Ipv6Inet : pair Ipv6InetPair : addr Ipv6Addr : native u128 : family Family::Ipv6}
+
+macro_rules! impl_arbitrary_for_ip {
+	($t:ident : v4 $v4:ident : v6 $v6:ident) => {
+		impl<'a> Arbitrary<'a> for $t {
+			fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+				if bool::arbitrary(u)? {
+					Ok(Self::V6($v6::arbitrary(u)?))
+				} else {
+					Ok(Self::V4($v4::arbitrary(u)?))
+				}
+			}
+
+			fn size_hint(depth: usize) -> (usize, Option<usize>) {
+				size_hint::and(
+					bool::size_hint(depth),
+					size_hint::or($v4::size_hint(depth), $v6::size_hint(depth)),
+				)
+			}
+		}
+	};
+}
+
+impl_arbitrary_for_ip! {IpCidr : v4 Ipv4Cidr : v6 Ipv6Cidr}
+impl_arbitrary_for_ip! {IpInet : v4 Ipv4Inet : v6 Ipv6Inet}
+impl_arbitrary_for_ip! {IpInetPair : v4 Ipv4InetPair : v6 Ipv6InetPair}
+
+impl<'a> Arbitrary<'a> for AnyIpCidr {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		Ok(match u.int_in_range(0u8..=2)? {
+			0 => Self::Any,
+			1 => Self::V4(Ipv4Cidr::arbitrary(u)?),
+			_ => Self::V6(Ipv6Cidr::arbitrary(u)?),
+		})
+	}
+
+	fn size_hint(depth: usize) -> (usize, Option<usize>) {
+		size_hint::and(
+			(0, Some(1)),
+			size_hint::or((0, Some(0)), IpCidr::size_hint(depth)),
+		)
+	}
+}
+
+impl<'a> Arbitrary<'a> for AnyIpInet {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		Ok(match u.int_in_range(0u8..=2)? {
+			0 => Self::Any,
+			1 => Self::V4(Ipv4Inet::arbitrary(u)?),
+			_ => Self::V6(Ipv6Inet::arbitrary(u)?),
+		})
+	}
+
+	fn size_hint(depth: usize) -> (usize, Option<usize>) {
+		size_hint::and(
+			(0, Some(1)),
+			size_hint::or((0, Some(0)), IpInet::size_hint(depth)),
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use arbitrary::{
+		Arbitrary,
+		Unstructured,
+	};
+
+	use crate::{
+		AnyIpCidr,
+		Cidr,
+		IpCidr,
+		IpInetPair,
+		Ipv4InetPair,
+		Ipv6InetPair,
+	};
+
+	fn check_cidr<C: Cidr>(c: C) {
+		assert_eq!(C::new(c.first_address(), c.network_length()), Ok(c));
+	}
+
+	#[test]
+	fn valid_values() {
+		let mut data = [0u8; 128];
+		let mut state = 0x1234_5678u32;
+		for _ in 0..200 {
+			for b in data.iter_mut() {
+				state = state.wrapping_mul(1103515245).wrapping_add(12345);
+				*b = (state >> 16) as u8;
+			}
+			let mut u = Unstructured::new(&data);
+			check_cidr(IpCidr::arbitrary(&mut u).unwrap());
+			if let AnyIpCidr::V4(c) = AnyIpCidr::arbitrary(&mut u).unwrap() {
+				check_cidr(c);
+			}
+			let p = Ipv4InetPair::arbitrary(&mut u).unwrap();
+			assert_eq!(p.first().network(), p.second().network());
+			let p = Ipv6InetPair::arbitrary(&mut u).unwrap();
+			assert_eq!(p.first().network(), p.second().network());
+			let p = IpInetPair::arbitrary(&mut u).unwrap();
+			assert_eq!(p.first().network(), p.second().network());
+		}
+	}
+
+	#[test]
+	fn empty_input() {
+		let mut u = Unstructured::new(&[]);
+		assert_eq!(AnyIpCidr::arbitrary(&mut u).unwrap(), AnyIpCidr::Any);
+		let mut u = Unstructured::new(&[]);
+		assert_eq!(
+			IpCidr::arbitrary(&mut u).unwrap(),
+			"0.0.0.0/0".parse::<IpCidr>().unwrap()
+		);
+	}
+}
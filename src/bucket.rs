@@ -0,0 +1,198 @@
+//! Grouping addresses into networks ("buckets"), e.g. for rate limiting
+//!
+//! A [`PrefixBucketer`] maps each address to the network of a configured
+//! length per family containing it; by default an IPv4 address is its
+//! own bucket, while all addresses of an IPv6 /64 (usually a single
+//! client) share a bucket.  A [`HierarchicalBucketer`] returns the
+//! buckets of several levels at once (e.g. /64, /56 and /48).
+//!
+//! Buckets can also be returned as [`PackedIpCidr`], a compact (17
+//! bytes, alignment 1) hashable key.
+//!
+//! ```
+//! use cidr::{
+//!     bucket::{HierarchicalBucketer, PrefixBucketer},
+//!     IpCidr,
+//! };
+//! use std::net::IpAddr;
+//!
+//! let addr: IpAddr = "2001:db8:1:2::1".parse().unwrap();
+//! assert_eq!(
+//!     PrefixBucketer::default().bucket(addr),
+//!     "2001:db8:1:2::/64".parse().unwrap()
+//! );
+//!
+//! let levels = HierarchicalBucketer::new([
+//!     PrefixBucketer::new(32, 64).unwrap(),
+//!     PrefixBucketer::new(24, 56).unwrap(),
+//!     PrefixBucketer::new(16, 48).unwrap(),
+//! ]);
+//! let [client, _site, customer] = levels.bucket_keys(addr);
+//! assert_eq!(client, levels.bucket_keys("2001:db8:1:2::2".parse().unwrap())[0]);
+//! assert_eq!(customer.get().unwrap(), "2001:db8:1::/48".parse::<IpCidr>().unwrap());
+//! ```
+
+use core::net::IpAddr;
+
+use crate::{
+	anonymize::Truncation,
+	errors::NetworkLengthTooLongError,
+	packed::PackedIpCidr,
+	Family,
+	IpCidr,
+};
+
+/// Maps addresses to the network of a configured length containing them
+///
+/// The buckets are the networks a [`Truncation`] truncates addresses to.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct PrefixBucketer(Truncation);
+
+impl PrefixBucketer {
+	/// Use networks of length `ipv4_length` for IPv4 addresses and of
+	/// length `ipv6_length` for IPv6 addresses as buckets
+	pub const fn new(ipv4_length: u8, ipv6_length: u8) -> Result<Self, NetworkLengthTooLongError> {
+		match Truncation::new(ipv4_length, ipv6_length) {
+			Ok(truncation) => Ok(Self(truncation)),
+			Err(e) => Err(e),
+		}
+	}
+
+	/// network length of IPv4 buckets
+	pub const fn ipv4_length(&self) -> u8 {
+		self.0.ipv4_length()
+	}
+
+	/// network length of IPv6 buckets
+	pub const fn ipv6_length(&self) -> u8 {
+		self.0.ipv6_length()
+	}
+
+	/// network length of buckets in the given family
+	pub const fn network_length(&self, family: Family) -> u8 {
+		self.0.network_length(family)
+	}
+
+	/// Bucket containing the address
+	pub const fn bucket(&self, addr: IpAddr) -> IpCidr {
+		self.0.network(addr)
+	}
+
+	/// Bucket containing the address as compact key
+	pub const fn bucket_key(&self, addr: IpAddr) -> PackedIpCidr {
+		PackedIpCidr::new(self.bucket(addr))
+	}
+}
+
+/// Uses /32 buckets for IPv4 and /64 buckets for IPv6
+impl Default for PrefixBucketer {
+	fn default() -> Self {
+		Self::new(32, 64).expect("valid network lengths")
+	}
+}
+
+/// Uses the networks addresses are truncated to as buckets
+impl From<Truncation> for PrefixBucketer {
+	fn from(truncation: Truncation) -> Self {
+		Self(truncation)
+	}
+}
+
+/// Maps addresses to the buckets of multiple [`PrefixBucketer`]s
+/// ("levels") at once
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct HierarchicalBucketer<const N: usize> {
+	levels: [PrefixBucketer; N],
+}
+
+impl<const N: usize> HierarchicalBucketer<N> {
+	/// Create new bucketer; buckets are returned in the order of the
+	/// levels
+	pub const fn new(levels: [PrefixBucketer; N]) -> Self {
+		Self { levels }
+	}
+
+	/// the levels
+	pub const fn levels(&self) -> &[PrefixBucketer; N] {
+		&self.levels
+	}
+
+	/// Buckets of all levels containing the address
+	pub fn buckets(&self, addr: IpAddr) -> [IpCidr; N] {
+		self.levels.map(|level| level.bucket(addr))
+	}
+
+	/// Buckets of all levels containing the address as compact keys
+	pub fn bucket_keys(&self, addr: IpAddr) -> [PackedIpCidr; N] {
+		self.levels.map(|level| level.bucket_key(addr))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn addr(s: &str) -> IpAddr {
+		s.parse().unwrap()
+	}
+
+	fn cidr(s: &str) -> IpCidr {
+		s.parse().unwrap()
+	}
+
+	#[test]
+	fn bucket() {
+		let b = PrefixBucketer::default();
+		assert_eq!(b.bucket(addr("192.0.2.1")), cidr("192.0.2.1/32"));
+		assert_eq!(b.bucket(addr("2001:db8::1")), cidr("2001:db8::/64"));
+		assert_eq!(
+			b.bucket_key(addr("2001:db8::1")),
+			b.bucket_key(addr("2001:db8::ffff"))
+		);
+		assert_ne!(
+			b.bucket_key(addr("2001:db8::1")),
+			b.bucket_key(addr("2001:db8:0:1::1"))
+		);
+		assert_eq!(
+			b.bucket_key(addr("192.0.2.1")).get(),
+			Ok(cidr("192.0.2.1/32"))
+		);
+
+		let b = PrefixBucketer::new(0, 128).unwrap();
+		assert_eq!(b.bucket(addr("192.0.2.1")), cidr("0.0.0.0/0"));
+		assert_eq!(b.bucket(addr("2001:db8::1")), cidr("2001:db8::1/128"));
+		assert!(PrefixBucketer::new(33, 64).is_err());
+		assert!(PrefixBucketer::new(32, 129).is_err());
+
+		let b = PrefixBucketer::from(Truncation::default());
+		assert_eq!(b.bucket(addr("192.0.2.1")), cidr("192.0.2.0/24"));
+	}
+
+	#[test]
+	fn hierarchical() {
+		let b = HierarchicalBucketer::new([
+			PrefixBucketer::new(32, 64).unwrap(),
+			PrefixBucketer::new(24, 56).unwrap(),
+			PrefixBucketer::new(16, 48).unwrap(),
+		]);
+		assert_eq!(
+			b.buckets(addr("2001:db8:1:2::1")),
+			[
+				cidr("2001:db8:1:2::/64"),
+				cidr("2001:db8:1::/56"),
+				cidr("2001:db8:1::/48"),
+			]
+		);
+		assert_eq!(
+			b.buckets(addr("192.0.2.1")),
+			[
+				cidr("192.0.2.1/32"),
+				cidr("192.0.2.0/24"),
+				cidr("192.0.0.0/16")
+			]
+		);
+		let keys = b.bucket_keys(addr("192.0.2.1"));
+		assert_eq!(keys[2].get(), Ok(cidr("192.0.0.0/16")));
+		assert_eq!(b.levels()[1].ipv6_length(), 56);
+	}
+}
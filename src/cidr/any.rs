@@ -10,11 +10,15 @@ use core::{
 
 use crate::{
 	errors::*,
+	internal_traits::PrivNetworkLike,
+	AnyIpCidrIterator,
 	Family,
+	InetIterator,
 	IpCidr,
 	IpInet,
 	Ipv4Cidr,
 	Ipv6Cidr,
+	NetworkLike,
 };
 
 /// Represents either an IPv4 or an IPv6 network or "any".
@@ -27,7 +31,8 @@ use crate::{
 /// Setting the first bit (using the `bitstring` API) always truncates
 /// the bit string to length 1 (i.e. `/0` in the resulting family).
 ///
-/// The [`Cidr`] trait cannot be implemented for this type.
+/// The [`Cidr`] trait cannot be implemented for this type; generic code
+/// accepting "any" as well as networks can use [`NetworkLike`] instead.
 ///
 /// [`Cidr`]: crate::Cidr
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
@@ -94,6 +99,31 @@ impl AnyIpCidr {
 		}
 	}
 
+	/// Iterate over all addresses in the network; for [`Any`] iterates
+	/// over all IPv4 addresses first (with network length `0`), then
+	/// over all IPv6 addresses.
+	///
+	/// [`Any`]: Self::Any
+	pub const fn iter(&self) -> AnyIpCidrIterator {
+		let (first, second) = match self {
+			Self::Any => (
+				IpCidr::V4(Ipv4Cidr {
+					address: Ipv4Addr::UNSPECIFIED,
+					network_length: 0,
+				})
+				.iter(),
+				IpCidr::V6(Ipv6Cidr {
+					address: Ipv6Addr::UNSPECIFIED,
+					network_length: 0,
+				})
+				.iter(),
+			),
+			Self::V4(c) => (IpCidr::V4(*c).iter(), InetIterator::_empty()),
+			Self::V6(c) => (IpCidr::V6(*c).iter(), InetIterator::_empty()),
+		};
+		AnyIpCidrIterator::_new(first, second)
+	}
+
 	/// first address in the network as plain address
 	///
 	/// returns [`None`] for [`Any`]
@@ -211,6 +241,39 @@ impl AnyIpCidr {
 			},
 		}
 	}
+
+	/// check whether all addresses of `other` are contained in the
+	/// network
+	///
+	/// [`Any`] contains all networks (but is only contained in
+	/// [`Any`]); networks of different families never contain each
+	/// other.
+	///
+	/// [`Any`]: Self::Any
+	pub fn contains_network<N: NetworkLike>(&self, other: &N) -> bool {
+		self._contains_network(&other.to_any_ip_cidr())
+	}
+
+	pub(crate) const fn _contains_network(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Self::Any, _) => true,
+			(Self::V4(a), Self::V4(b)) => {
+				a.network_length <= b.network_length && a.contains(&b.address)
+			},
+			(Self::V6(a), Self::V6(b)) => {
+				a.network_length <= b.network_length && a.contains(&b.address)
+			},
+			_ => false,
+		}
+	}
+}
+
+impl PrivNetworkLike for AnyIpCidr {}
+
+impl NetworkLike for AnyIpCidr {
+	fn to_any_ip_cidr(&self) -> AnyIpCidr {
+		*self
+	}
 }
 
 impl fmt::Display for AnyIpCidr {
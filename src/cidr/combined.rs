@@ -10,7 +10,11 @@ use core::{
 
 use crate::{
 	errors::*,
-	internal_traits::PrivCidr,
+	internal_traits::{
+		PrivCidr,
+		PrivNetworkLike,
+	},
+	AnyIpCidr,
 	Cidr,
 	Family,
 	InetIterator,
@@ -19,6 +23,7 @@ use crate::{
 	IpInetPair,
 	Ipv4Cidr,
 	Ipv6Cidr,
+	NetworkLike,
 };
 
 impl IpCidr {
@@ -66,6 +71,27 @@ impl IpCidr {
 		}
 	}
 
+	/// Create new network of the given family from the numeric
+	/// representation of the first address and prefix length
+	///
+	/// See [`Family::cidr_from_bits`]; fails with
+	/// [`NetworkParseError::AddressBitsOutOfRange`] if `bits` doesn't fit
+	/// into an IPv4 address.
+	pub const fn from_bits(family: Family, bits: u128, len: u8) -> Result<Self, NetworkParseError> {
+		family.cidr_from_bits(bits, len)
+	}
+
+	/// Numeric representation of the first address and the network length
+	pub const fn to_bits(&self) -> (u128, u8) {
+		match self {
+			Self::V4(c) => {
+				let (bits, len) = c.to_bits();
+				(bits as u128, len)
+			},
+			Self::V6(c) => c.to_bits(),
+		}
+	}
+
 	/// Iterate over all addresses in the range.  With IPv6 addresses
 	/// this can produce really long iterations (up to 2<sup>128</sup>
 	/// addresses).
@@ -155,6 +181,56 @@ impl IpCidr {
 		}
 	}
 
+	/// address with the given index in the network (index `0` is the
+	/// first address) as plain address
+	///
+	/// Returns `None` if the index is too large for the network.
+	pub const fn nth_address(&self, index: u128) -> Option<IpAddr> {
+		match self.nth(index) {
+			Some(inet) => Some(inet.address()),
+			None => None,
+		}
+	}
+
+	/// address with the given index in the network (index `0` is the
+	/// first address)
+	///
+	/// Returns `None` if the index is too large for the network.
+	pub const fn nth(&self, index: u128) -> Option<IpInet> {
+		self.first().checked_add(index)
+	}
+
+	/// address with the given index counted from the end of the network
+	/// (index `0` is the last address) as plain address
+	///
+	/// Returns `None` if the index is too large for the network.
+	pub const fn nth_address_from_end(&self, index: u128) -> Option<IpAddr> {
+		match self.nth_from_end(index) {
+			Some(inet) => Some(inet.address()),
+			None => None,
+		}
+	}
+
+	/// address with the given index counted from the end of the network
+	/// (index `0` is the last address)
+	///
+	/// Returns `None` if the index is too large for the network.
+	pub const fn nth_from_end(&self, index: u128) -> Option<IpInet> {
+		self.last().checked_sub(index)
+	}
+
+	/// index of the address in the network (the first address has index
+	/// `0`)
+	///
+	/// Returns `None` if the address is not contained in the network.
+	pub const fn index_of(&self, addr: &IpAddr) -> Option<u128> {
+		match (self, addr) {
+			(Self::V4(c), IpAddr::V4(a)) => c.index_of(a),
+			(Self::V6(c), IpAddr::V6(a)) => c.index_of(a),
+			_ => None,
+		}
+	}
+
 	pub(crate) const fn _range_pair(&self) -> IpInetPair {
 		match self {
 			Self::V4(c) => IpInetPair::V4(c._range_pair()),
@@ -163,6 +239,14 @@ impl IpCidr {
 	}
 }
 
+impl PrivNetworkLike for IpCidr {}
+
+impl NetworkLike for IpCidr {
+	fn to_any_ip_cidr(&self) -> AnyIpCidr {
+		(*self).into()
+	}
+}
+
 impl PrivCidr for IpCidr {}
 
 impl Cidr for IpCidr {
@@ -180,6 +264,10 @@ impl Cidr for IpCidr {
 		self.iter()
 	}
 
+	fn hosts(&self) -> InetIterator<IpAddr> {
+		self.hosts()
+	}
+
 	fn first_address(&self) -> IpAddr {
 		self.first_address()
 	}
@@ -215,6 +303,26 @@ impl Cidr for IpCidr {
 	fn contains(&self, addr: &IpAddr) -> bool {
 		self.contains(addr)
 	}
+
+	fn nth_address(&self, index: u128) -> Option<IpAddr> {
+		self.nth_address(index)
+	}
+
+	fn nth(&self, index: u128) -> Option<IpInet> {
+		self.nth(index)
+	}
+
+	fn nth_address_from_end(&self, index: u128) -> Option<IpAddr> {
+		self.nth_address_from_end(index)
+	}
+
+	fn nth_from_end(&self, index: u128) -> Option<IpInet> {
+		self.nth_from_end(index)
+	}
+
+	fn index_of(&self, addr: &IpAddr) -> Option<u128> {
+		self.index_of(addr)
+	}
 }
 
 impl fmt::Display for IpCidr {
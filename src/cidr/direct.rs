@@ -14,21 +14,25 @@ use crate::{
 	errors::*,
 	internal_traits::{
 		PrivCidr,
+		PrivNetworkLike,
 		PrivUnspecAddress,
 	},
+	AnyIpCidr,
 	Cidr,
 	Family,
 	InetIterator,
+	IpInet,
 	Ipv4Cidr,
 	Ipv4Inet,
 	Ipv4InetPair,
 	Ipv6Cidr,
 	Ipv6Inet,
 	Ipv6InetPair,
+	NetworkLike,
 };
 
 macro_rules! impl_cidr_for {
-	($n:ident : inet $inet:ident : addr $addr:ident : pair $pair:ident : family $family:expr) => {
+	($n:ident : inet $inet:ident : addr $addr:ident : pair $pair:ident : variant $variant:ident : native $native:ident : family $family:expr) => {
 		#[cfg(feature = "bitstring")]
 		impl bitstring::BitString for $n {
 			fn get(&self, ndx: usize) -> bool {
@@ -89,7 +93,12 @@ macro_rules! impl_cidr_for {
 						NetworkLengthTooLongError::new(len as usize, $family),
 					))
 				} else if !<$addr as PrivUnspecAddress>::_Tools::_has_zero_host_part(addr, len) {
-					Err(NetworkParseError::InvalidHostPart)
+					Err(NetworkParseError::InvalidHostPart(
+						InvalidHostPartError::new(IpInet::$variant($inet {
+							address: addr,
+							network_length: len,
+						})),
+					))
 				} else {
 					Ok(Self {
 						address: addr,
@@ -107,6 +116,21 @@ macro_rules! impl_cidr_for {
 				}
 			}
 
+			/// Create new network from the numeric representation of the
+			/// first address and prefix length; fails like [`new`](Self::new).
+			pub const fn from_bits(bits: $native, len: u8) -> Result<Self, NetworkParseError> {
+				Self::new(<$addr as PrivUnspecAddress>::_Tools::from_native(bits), len)
+			}
+
+			/// Numeric representation of the first address and the network
+			/// length
+			pub const fn to_bits(&self) -> ($native, u8) {
+				(
+					<$addr as PrivUnspecAddress>::_Tools::to_native(self.address),
+					self.network_length,
+				)
+			}
+
 			/// Iterate over all addresses in the range.  With IPv6 addresses
 			/// this can produce really long iterations (up to 2<sup>128</sup>
 			/// addresses).
@@ -176,6 +200,60 @@ macro_rules! impl_cidr_for {
 				)
 			}
 
+			/// address with the given index in the network (index `0` is
+			/// the first address) as plain address
+			///
+			/// Returns `None` if the index is too large for the network.
+			pub const fn nth_address(&self, index: u128) -> Option<$addr> {
+				match self.nth(index) {
+					Some(inet) => Some(inet.address),
+					None => None,
+				}
+			}
+
+			/// address with the given index in the network (index `0` is
+			/// the first address)
+			///
+			/// Returns `None` if the index is too large for the network.
+			pub const fn nth(&self, index: u128) -> Option<$inet> {
+				self.first().checked_add(index)
+			}
+
+			/// address with the given index counted from the end of the
+			/// network (index `0` is the last address) as plain address
+			///
+			/// Returns `None` if the index is too large for the network.
+			pub const fn nth_address_from_end(&self, index: u128) -> Option<$addr> {
+				match self.nth_from_end(index) {
+					Some(inet) => Some(inet.address),
+					None => None,
+				}
+			}
+
+			/// address with the given index counted from the end of the
+			/// network (index `0` is the last address)
+			///
+			/// Returns `None` if the index is too large for the network.
+			pub const fn nth_from_end(&self, index: u128) -> Option<$inet> {
+				self.last().checked_sub(index)
+			}
+
+			/// index of the address in the network (the first address has
+			/// index `0`)
+			///
+			/// Returns `None` if the address is not contained in the
+			/// network.
+			pub const fn index_of(&self, addr: &$addr) -> Option<u128> {
+				if self.contains(addr) {
+					Some(<$addr as PrivUnspecAddress>::_Tools::_host_part(
+						*addr,
+						self.network_length,
+					))
+				} else {
+					None
+				}
+			}
+
 			pub(crate) const fn _range_pair(&self) -> $pair {
 				$pair {
 					first: self.first_address(),
@@ -185,6 +263,14 @@ macro_rules! impl_cidr_for {
 			}
 		}
 
+		impl PrivNetworkLike for $n {}
+
+		impl NetworkLike for $n {
+			fn to_any_ip_cidr(&self) -> AnyIpCidr {
+				AnyIpCidr::$variant(*self)
+			}
+		}
+
 		impl PrivCidr for $n {}
 
 		impl Cidr for $n {
@@ -202,6 +288,10 @@ macro_rules! impl_cidr_for {
 				self.iter()
 			}
 
+			fn hosts(&self) -> InetIterator<$addr> {
+				self.hosts()
+			}
+
 			fn first_address(&self) -> $addr {
 				self.first_address()
 			}
@@ -237,6 +327,26 @@ macro_rules! impl_cidr_for {
 			fn contains(&self, addr: &$addr) -> bool {
 				self.contains(addr)
 			}
+
+			fn nth_address(&self, index: u128) -> Option<$addr> {
+				self.nth_address(index)
+			}
+
+			fn nth(&self, index: u128) -> Option<$inet> {
+				self.nth(index)
+			}
+
+			fn nth_address_from_end(&self, index: u128) -> Option<$addr> {
+				self.nth_address_from_end(index)
+			}
+
+			fn nth_from_end(&self, index: u128) -> Option<$inet> {
+				self.nth_from_end(index)
+			}
+
+			fn index_of(&self, addr: &$addr) -> Option<u128> {
+				self.index_of(addr)
+			}
 		}
 
 		impl fmt::Debug for $n {
@@ -275,6 +385,7 @@ macro_rules! impl_cidr_for {
 
 			fn from_str(s: &str) -> Result<$n, NetworkParseError> {
 				crate::parsers::parse_cidr(s, FromStr::from_str)
+					.map_err(|e| e.check_family(s, $family))
 			}
 		}
 
@@ -284,6 +395,20 @@ macro_rules! impl_cidr_for {
 			}
 		}
 
+		impl From<$n> for ($native, u8) {
+			fn from(c: $n) -> Self {
+				c.to_bits()
+			}
+		}
+
+		impl TryFrom<($native, u8)> for $n {
+			type Error = NetworkParseError;
+
+			fn try_from((bits, len): ($native, u8)) -> Result<Self, NetworkParseError> {
+				Self::from_bits(bits, len)
+			}
+		}
+
 		/// Iterate over all the addresses in the CIDR.
 		impl IntoIterator for $n {
 			type IntoIter = $crate::InetIterator<$addr>;
@@ -296,8 +421,8 @@ macro_rules! impl_cidr_for {
 	};
 }
 
-impl_cidr_for! {Ipv4Cidr : inet Ipv4Inet : addr Ipv4Addr : pair Ipv4InetPair : family Family::Ipv4}
-impl_cidr_for! {Ipv6Cidr : inet Ipv6Inet : addr Ipv6Addr : pair Ipv6InetPair : family Family::Ipv6}
+impl_cidr_for! {Ipv4Cidr : inet Ipv4Inet : addr Ipv4Addr : pair Ipv4InetPair : variant V4 : native u32 : family Family::Ipv4}
+impl_cidr_for! {Ipv6Cidr : inet Ipv6Inet : addr Ipv6Addr : pair Ipv6InetPair : variant V6 : native u128 : family Family::Ipv6}
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,146 @@
+// usable host addresses in networks: IPv4 network and broadcast addresses
+// (RFC 919, RFC 3021), IPv6 subnet-router anycast address (RFC 4291,
+// RFC 6164) and reserved subnet anycast addresses (RFC 2526)
+
+use core::{
+	iter::Chain,
+	net::{
+		IpAddr,
+		Ipv4Addr,
+		Ipv6Addr,
+	},
+};
+
+use crate::{
+	internal_traits::PrivUnspecAddress,
+	InetIterator,
+	IpCidr,
+	IpInetPair,
+	Ipv4Cidr,
+	Ipv4InetPair,
+	Ipv6Cidr,
+	Ipv6InetPair,
+};
+
+// number of reserved subnet anycast addresses (RFC 2526)
+const RESERVED_ANYCAST: u128 = 128;
+
+// first reserved subnet anycast interface identifier in EUI-64 format
+// (with the universal/local bit set to zero)
+const RESERVED_ANYCAST_EUI64: u128 = 0xfdff_ffff_ffff_ff80;
+
+impl Ipv4Cidr {
+	/// network address (first address in the network)
+	///
+	/// Same as [`first_address`](Self::first_address).
+	pub const fn network_address(&self) -> Ipv4Addr {
+		self.first_address()
+	}
+
+	/// broadcast address (last address in the network)
+	///
+	/// Returns `None` for /31 (point-to-point links, RFC 3021) and /32
+	/// networks, which don't have a broadcast address.
+	pub const fn broadcast(&self) -> Option<Ipv4Addr> {
+		if self.network_length >= 31 {
+			None
+		} else {
+			Some(self.last_address())
+		}
+	}
+
+	/// Iterate over the addresses usable for hosts
+	///
+	/// Excludes the network and the broadcast address, apart from /31
+	/// (point-to-point links, RFC 3021) and /32 networks, where all
+	/// addresses are usable.
+	pub const fn hosts(&self) -> InetIterator<Ipv4Addr> {
+		self._hosts_pair().iter()
+	}
+
+	const fn _hosts_pair(&self) -> Ipv4InetPair {
+		if self.network_length >= 31 {
+			return self._range_pair();
+		}
+		let first = <Ipv4Addr as PrivUnspecAddress>::_Tools::to_native(self.first_address());
+		let last = <Ipv4Addr as PrivUnspecAddress>::_Tools::to_native(self.last_address());
+		Ipv4InetPair {
+			first: <Ipv4Addr as PrivUnspecAddress>::_Tools::from_native(first + 1),
+			second: <Ipv4Addr as PrivUnspecAddress>::_Tools::from_native(last - 1),
+			network_length: self.network_length,
+		}
+	}
+}
+
+impl Ipv6Cidr {
+	const fn _host_range(&self, first: u128, last: u128) -> Ipv6InetPair {
+		Ipv6InetPair {
+			first: <Ipv6Addr as PrivUnspecAddress>::_Tools::from_native(first),
+			second: <Ipv6Addr as PrivUnspecAddress>::_Tools::from_native(last),
+			network_length: self.network_length,
+		}
+	}
+
+	/// Iterate over the addresses usable for hosts
+	///
+	/// Excludes the subnet-router anycast address (the first address),
+	/// apart from /127 (point-to-point links, RFC 6164) and /128 networks,
+	/// where all addresses are usable.
+	///
+	/// See [`hosts_without_reserved_anycast`] to also exclude the
+	/// reserved subnet anycast addresses.
+	///
+	/// [`hosts_without_reserved_anycast`]: Self::hosts_without_reserved_anycast
+	pub const fn hosts(&self) -> InetIterator<Ipv6Addr> {
+		self._hosts_pair().iter()
+	}
+
+	const fn _hosts_pair(&self) -> Ipv6InetPair {
+		if self.network_length >= 127 {
+			return self._range_pair();
+		}
+		let first = <Ipv6Addr as PrivUnspecAddress>::_Tools::to_native(self.first_address());
+		let last = <Ipv6Addr as PrivUnspecAddress>::_Tools::to_native(self.last_address());
+		self._host_range(first + 1, last)
+	}
+
+	/// Iterate over the addresses usable for hosts, also excluding the
+	/// reserved subnet anycast addresses (RFC 2526)
+	///
+	/// In /64 networks the reserved interface identifiers are
+	/// `fdff:ffff:ffff:ff80` to `fdff:ffff:ffff:ffff` (EUI-64 format); in
+	/// other networks up to /120 the last 128 addresses are reserved.
+	/// Smaller networks don't have reserved subnet anycast addresses.
+	///
+	/// Also excludes the subnet-router anycast address like
+	/// [`hosts`](Self::hosts).
+	pub fn hosts_without_reserved_anycast(
+		&self,
+	) -> Chain<InetIterator<Ipv6Addr>, InetIterator<Ipv6Addr>> {
+		let first = <Ipv6Addr as PrivUnspecAddress>::_Tools::to_native(self.first_address());
+		let last = <Ipv6Addr as PrivUnspecAddress>::_Tools::to_native(self.last_address());
+		if self.network_length == 64 {
+			let reserved = first + RESERVED_ANYCAST_EUI64;
+			let before = self._host_range(first + 1, reserved - 1);
+			let after = self._host_range(reserved + RESERVED_ANYCAST, last);
+			before.iter().chain(after.iter())
+		} else if self.network_length <= 120 {
+			let hosts = self._host_range(first + 1, last - RESERVED_ANYCAST);
+			hosts.iter().chain(InetIterator::_empty())
+		} else {
+			self.hosts().chain(InetIterator::_empty())
+		}
+	}
+}
+
+impl IpCidr {
+	/// Iterate over the addresses usable for hosts
+	///
+	/// See [`Ipv4Cidr::hosts`] and [`Ipv6Cidr::hosts`].
+	pub const fn hosts(&self) -> InetIterator<IpAddr> {
+		match self {
+			Self::V4(c) => IpInetPair::V4(c._hosts_pair()).iter(),
+			Self::V6(c) => IpInetPair::V6(c._hosts_pair()).iter(),
+		}
+	}
+}
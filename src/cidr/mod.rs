@@ -1,8 +1,13 @@
-pub use self::any::AnyIpCidr;
+pub use self::{
+	any::AnyIpCidr,
+	nlri::NlriIterator,
+};
 
 mod any;
 mod combined;
 mod direct;
+mod hosts;
+mod nlri;
 mod serde;
 
 #[cfg(feature = "bitstring")]
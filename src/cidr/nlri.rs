@@ -0,0 +1,282 @@
+// BGP NLRI prefix encoding (RFC 4271, section 4.3): a length byte followed
+// by the minimum number of octets needed to hold `length` bits.
+
+use core::{
+	iter::FusedIterator,
+	marker::PhantomData,
+	net::{
+		Ipv4Addr,
+		Ipv6Addr,
+	},
+};
+
+use crate::{
+	errors::*,
+	internal_traits::PrivUnspecAddress,
+	Family,
+	Ipv4Cidr,
+	Ipv6Cidr,
+};
+
+/// Iterator decoding a packed list of prefixes in the BGP NLRI encoding
+///
+/// Created by [`Ipv4Cidr::parse_nlri`] and [`Ipv6Cidr::parse_nlri`].
+///
+/// Yields an error (and stops afterwards) if a prefix is invalid or the
+/// input ends in the middle of a prefix.
+#[derive(Clone, Debug)]
+pub struct NlriIterator<'a, C> {
+	data: &'a [u8],
+	_marker: PhantomData<C>,
+}
+
+impl<'a, C> NlriIterator<'a, C> {
+	const fn new(data: &'a [u8]) -> Self {
+		Self {
+			data,
+			_marker: PhantomData,
+		}
+	}
+
+	/// Remaining (not yet decoded) input
+	pub const fn remaining(&self) -> &'a [u8] {
+		self.data
+	}
+}
+
+macro_rules! impl_nlri_for {
+	($n:ident : addr $addr:ident : native $native:ident : octets $octets:literal : family $family:expr) => {
+		impl $n {
+			/// Length of the BGP NLRI encoding of the network: a length byte
+			/// followed by the significant octets of the address.
+			pub const fn nlri_len(&self) -> usize {
+				1 + (self.network_length as usize).div_ceil(8)
+			}
+
+			/// Decode a single prefix in the BGP NLRI encoding from the
+			/// start of `data`.
+			///
+			/// Returns the network and the number of bytes consumed; any
+			/// remaining data is not inspected.
+			pub const fn read_nlri(data: &[u8]) -> Result<(Self, usize), NlriDecodeError> {
+				if data.is_empty() {
+					return Err(NlriDecodeError::UnexpectedEnd);
+				}
+				let len = data[0];
+				if len > $family.len() {
+					return Err(NlriDecodeError::NetworkLengthTooLongError(
+						NetworkLengthTooLongError::new(len as usize, $family),
+					));
+				}
+				let used = (len as usize).div_ceil(8);
+				if data.len() < 1 + used {
+					return Err(NlriDecodeError::UnexpectedEnd);
+				}
+				let mut octets = [0u8; $octets];
+				let mut i = 0;
+				while i < used {
+					octets[i] = data[1 + i];
+					i += 1;
+				}
+				let address = <$addr as PrivUnspecAddress>::_Tools::from_native(
+					$native::from_be_bytes(octets),
+				);
+				if !<$addr as PrivUnspecAddress>::_Tools::_has_zero_host_part(address, len) {
+					return Err(NlriDecodeError::InvalidHostPart);
+				}
+				Ok((
+					Self {
+						address,
+						network_length: len,
+					},
+					1 + used,
+				))
+			}
+
+			/// Decode a single prefix in the BGP NLRI encoding
+			///
+			/// Fails with [`NlriDecodeError::TrailingData`] unless `data`
+			/// contains exactly one prefix.
+			pub const fn from_nlri(data: &[u8]) -> Result<Self, NlriDecodeError> {
+				match Self::read_nlri(data) {
+					Ok((cidr, used)) => {
+						if used == data.len() {
+							Ok(cidr)
+						} else {
+							Err(NlriDecodeError::TrailingData)
+						}
+					},
+					Err(e) => Err(e),
+				}
+			}
+
+			/// Iterate over a packed list of prefixes in the BGP NLRI
+			/// encoding (e.g. the NLRI or withdrawn routes field of a BGP
+			/// UPDATE message)
+			pub const fn parse_nlri(data: &[u8]) -> NlriIterator<'_, Self> {
+				NlriIterator::new(data)
+			}
+
+			/// Write the network in the BGP NLRI encoding to the start of
+			/// `buf`
+			///
+			/// Returns the number of bytes written (see [`nlri_len`]).
+			///
+			/// [`nlri_len`]: Self::nlri_len
+			pub fn write_nlri(&self, buf: &mut [u8]) -> Result<usize, NlriBufferTooSmallError> {
+				let len = self.nlri_len();
+				let buf = buf.get_mut(..len).ok_or(NlriBufferTooSmallError::new())?;
+				buf[0] = self.network_length;
+				let octets = self.address.octets();
+				buf[1..].copy_from_slice(&octets[..len - 1]);
+				Ok(len)
+			}
+
+			/// Write a list of networks in the BGP NLRI encoding to the
+			/// start of `buf`
+			///
+			/// Returns the number of bytes written.  If `buf` is too small
+			/// the content of `buf` is unspecified.
+			pub fn write_nlri_list<I>(
+				list: I,
+				buf: &mut [u8],
+			) -> Result<usize, NlriBufferTooSmallError>
+			where
+				I: IntoIterator<Item = Self>,
+			{
+				let mut pos = 0;
+				for cidr in list {
+					pos += cidr.write_nlri(&mut buf[pos..])?;
+				}
+				Ok(pos)
+			}
+		}
+
+		impl Iterator for NlriIterator<'_, $n> {
+			type Item = Result<$n, NlriDecodeError>;
+
+			fn next(&mut self) -> Option<Self::Item> {
+				if self.data.is_empty() {
+					return None;
+				}
+				match $n::read_nlri(self.data) {
+					Ok((cidr, used)) => {
+						self.data = &self.data[used..];
+						Some(Ok(cidr))
+					},
+					Err(e) => {
+						self.data = &[];
+						Some(Err(e))
+					},
+				}
+			}
+		}
+
+		impl FusedIterator for NlriIterator<'_, $n> {}
+	};
+}
+
+impl_nlri_for! {Ipv4Cidr : addr Ipv4Addr : native u32 : octets 4 : family Family::Ipv4}
+impl_nlri_for! {Ipv6Cidr : addr Ipv6Addr : native u128 : octets 16 : family Family::Ipv6}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		errors::NlriDecodeError,
+		Ipv4Cidr,
+		Ipv6Cidr,
+	};
+
+	fn test_v4(s: &str, encoded: &[u8]) {
+		let cidr = s.parse::<Ipv4Cidr>().unwrap();
+		assert_eq!(cidr.nlri_len(), encoded.len());
+		let mut buf = [0xaau8; 8];
+		assert_eq!(cidr.write_nlri(&mut buf), Ok(encoded.len()));
+		assert_eq!(&buf[..encoded.len()], encoded);
+		assert_eq!(Ipv4Cidr::from_nlri(encoded), Ok(cidr));
+	}
+
+	fn test_v6(s: &str, encoded: &[u8]) {
+		let cidr = s.parse::<Ipv6Cidr>().unwrap();
+		assert_eq!(cidr.nlri_len(), encoded.len());
+		let mut buf = [0xaau8; 20];
+		assert_eq!(cidr.write_nlri(&mut buf), Ok(encoded.len()));
+		assert_eq!(&buf[..encoded.len()], encoded);
+		assert_eq!(Ipv6Cidr::from_nlri(encoded), Ok(cidr));
+	}
+
+	#[test]
+	fn encode_decode_v4() {
+		test_v4("0.0.0.0/0", &[0]);
+		test_v4("10.0.0.0/8", &[8, 10]);
+		test_v4("192.0.2.0/24", &[24, 192, 0, 2]);
+		test_v4("192.0.2.128/25", &[25, 192, 0, 2, 128]);
+		test_v4("192.0.2.1/32", &[32, 192, 0, 2, 1]);
+	}
+
+	#[test]
+	fn encode_decode_v6() {
+		test_v6("::/0", &[0]);
+		test_v6("2001:db8::/32", &[32, 0x20, 0x01, 0x0d, 0xb8]);
+		test_v6("2001:db8:8000::/33", &[33, 0x20, 0x01, 0x0d, 0xb8, 0x80]);
+		test_v6(
+			"2001:db8::1/128",
+			&[
+				128, 0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+			],
+		);
+	}
+
+	#[test]
+	fn decode_errors() {
+		assert_eq!(
+			Ipv4Cidr::from_nlri(&[]),
+			Err(NlriDecodeError::UnexpectedEnd)
+		);
+		assert_eq!(
+			Ipv4Cidr::from_nlri(&[24, 192, 0]),
+			Err(NlriDecodeError::UnexpectedEnd)
+		);
+		assert_eq!(
+			Ipv4Cidr::from_nlri(&[7, 11]),
+			Err(NlriDecodeError::InvalidHostPart)
+		);
+		assert_eq!(
+			Ipv4Cidr::from_nlri(&[8, 10, 0]),
+			Err(NlriDecodeError::TrailingData)
+		);
+		assert!(matches!(
+			Ipv4Cidr::from_nlri(&[33, 1, 2, 3, 4, 5]),
+			Err(NlriDecodeError::NetworkLengthTooLongError(_))
+		));
+		assert!(matches!(
+			Ipv6Cidr::from_nlri(&[129]),
+			Err(NlriDecodeError::NetworkLengthTooLongError(_))
+		));
+	}
+
+	#[test]
+	fn list() {
+		let list = [
+			"10.0.0.0/8".parse::<Ipv4Cidr>().unwrap(),
+			"192.0.2.0/24".parse::<Ipv4Cidr>().unwrap(),
+			"0.0.0.0/0".parse::<Ipv4Cidr>().unwrap(),
+		];
+		let mut buf = [0u8; 7];
+		assert_eq!(Ipv4Cidr::write_nlri_list(list, &mut buf), Ok(7));
+		assert_eq!(buf, [8, 10, 24, 192, 0, 2, 0]);
+		assert!(Ipv4Cidr::write_nlri_list(list, &mut buf[..6]).is_err());
+
+		let mut iter = Ipv4Cidr::parse_nlri(&buf);
+		for cidr in list {
+			assert_eq!(iter.next(), Some(Ok(cidr)));
+		}
+		assert_eq!(iter.next(), None);
+
+		// trailing garbage
+		let mut iter = Ipv4Cidr::parse_nlri(&[8, 10, 24, 192]);
+		assert_eq!(iter.next(), Some(Ok(list[0])));
+		assert_eq!(iter.next(), Some(Err(NlriDecodeError::UnexpectedEnd)));
+		assert_eq!(iter.next(), None);
+	}
+}
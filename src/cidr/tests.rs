@@ -8,9 +8,13 @@ use core::{
 };
 
 use crate::{
+	AnyIpCidr,
+	Cidr,
+	Inet,
 	IpCidr,
 	Ipv4Cidr,
 	Ipv6Cidr,
+	NetworkLike,
 };
 
 fn test_v4(s: &'static str, first_addr: Ipv4Addr, last_addr: Ipv4Addr, mask: Ipv4Addr, l: u8) {
@@ -284,17 +288,13 @@ fn test_order(o: Ordering, a: &'static str, b: &'static str) {
 }
 
 #[test]
-#[should_panic(
-	expected = "invalid length for network: Network length 33 is too long for Ipv4 (maximum: 32)"
-)]
+#[should_panic(expected = "NetworkLengthTooLongError { network_length: 33, family: Ipv4 }")]
 fn parse_v4_33bit() {
 	"192.0.2.48/33".parse::<Ipv4Cidr>().unwrap();
 }
 
 #[test]
-#[should_panic(
-	expected = "invalid length for network: Network length 33 is too long for Ipv4 (maximum: 32)"
-)]
+#[should_panic(expected = "NetworkLengthTooLongError { network_length: 33, family: Ipv4 }")]
 fn parse_v4_33bit_2() {
 	"192.0.2.48/33".parse::<IpCidr>().unwrap();
 }
@@ -426,7 +426,7 @@ fn parse_v4_23bit() {
 }
 
 #[test]
-#[should_panic(expected = "host part of address was not zero")]
+#[should_panic(expected = "InvalidHostPart(")]
 fn parse_v4_23bit_non_zero_host_bits() {
 	"192.0.3.0/23".parse::<Ipv4Cidr>().unwrap();
 }
@@ -443,7 +443,7 @@ fn parse_v4_17bit() {
 }
 
 #[test]
-#[should_panic(expected = "host part of address was not zero")]
+#[should_panic(expected = "InvalidHostPart(")]
 fn parse_v4_17bit_non_zero_host_bits() {
 	"192.0.192.0/17".parse::<Ipv4Cidr>().unwrap();
 }
@@ -460,7 +460,7 @@ fn parse_v4_8bit() {
 }
 
 #[test]
-#[should_panic(expected = "invalid IPv4 address syntax")]
+#[should_panic(expected = "AddrParseError(")]
 fn parse_v4_8bit_short() {
 	test_v4(
 		"10/8",
@@ -483,7 +483,7 @@ fn parse_v4_0bit() {
 }
 
 #[test]
-#[should_panic(expected = "invalid IPv4 address syntax")]
+#[should_panic(expected = "AddrParseError(")]
 fn parse_v4_0bit_short() {
 	test_v4(
 		"0/0",
@@ -495,7 +495,7 @@ fn parse_v4_0bit_short() {
 }
 
 #[test]
-#[should_panic(expected = "host part of address was not zero")]
+#[should_panic(expected = "InvalidHostPart(")]
 fn parse_v4_non_zero_host_bits() {
 	"10.1.1.1/24".parse::<Ipv4Cidr>().unwrap();
 }
@@ -516,17 +516,13 @@ fn contains_not_v4_24bit_2() {
 }
 
 #[test]
-#[should_panic(
-	expected = "invalid length for network: Network length 129 is too long for Ipv6 (maximum: 128)"
-)]
+#[should_panic(expected = "NetworkLengthTooLongError { network_length: 129, family: Ipv6 }")]
 fn parse_v6_129bit() {
 	"2001:DB8::/129".parse::<Ipv6Cidr>().unwrap();
 }
 
 #[test]
-#[should_panic(
-	expected = "invalid length for network: Network length 129 is too long for Ipv6 (maximum: 128)"
-)]
+#[should_panic(expected = "NetworkLengthTooLongError { network_length: 129, family: Ipv6 }")]
 fn parse_v6_33bit_2() {
 	"2001:DB8::/129".parse::<IpCidr>().unwrap();
 }
@@ -693,7 +689,7 @@ fn parse_v6_64bit() {
 }
 
 #[test]
-#[should_panic(expected = "host part of address was not zero")]
+#[should_panic(expected = "InvalidHostPart(")]
 fn parse_v6_non_zero_host_bits() {
 	"2001:DB8:1234:5678:1::/64".parse::<Ipv6Cidr>().unwrap();
 }
@@ -769,3 +765,216 @@ fn order_v6() {
 fn order() {
 	test_order(Ordering::Less, "192.0.2.0/24", "2001:DB8:1234:5678::/64");
 }
+
+fn test_nth<C: Cidr>(s: &'static str, index: u128, addr: &'static str, rev_index: u128) {
+	let cidr = s.parse::<C>().unwrap();
+	let addr = addr.parse::<C::Address>().unwrap();
+	assert_eq!(cidr.nth_address(index), Some(addr));
+	assert_eq!(cidr.nth(index).unwrap().address(), addr);
+	assert_eq!(cidr.nth(index).unwrap().network(), cidr);
+	assert_eq!(cidr.nth_address_from_end(rev_index), Some(addr));
+	assert_eq!(cidr.nth_from_end(rev_index), cidr.nth(index));
+	assert_eq!(cidr.index_of(&addr), Some(index));
+}
+
+#[test]
+fn nth_v4() {
+	test_nth::<Ipv4Cidr>("192.0.2.0/24", 0, "192.0.2.0", 255);
+	test_nth::<Ipv4Cidr>("192.0.2.0/24", 1, "192.0.2.1", 254);
+	test_nth::<Ipv4Cidr>("192.0.2.0/24", 254, "192.0.2.254", 1);
+	test_nth::<IpCidr>("192.0.2.0/24", 2, "192.0.2.2", 253);
+	test_nth::<Ipv4Cidr>("0.0.0.0/0", 0xffff_ffff, "255.255.255.255", 0);
+	test_nth::<Ipv4Cidr>("192.0.2.1/32", 0, "192.0.2.1", 0);
+
+	let cidr = "192.0.2.0/24".parse::<Ipv4Cidr>().unwrap();
+	assert_eq!(cidr.nth(256), None);
+	assert_eq!(cidr.nth_address(1 << 32), None);
+	assert_eq!(cidr.nth_from_end(256), None);
+	assert_eq!(cidr.index_of(&Ipv4Addr::new(192, 0, 3, 0)), None);
+
+	let cidr = "192.0.2.0/24".parse::<IpCidr>().unwrap();
+	assert_eq!(cidr.nth(256), None);
+	assert_eq!(cidr.index_of(&IpAddr::V6(Ipv6Addr::UNSPECIFIED)), None);
+}
+
+#[test]
+fn nth_v6() {
+	test_nth::<Ipv6Cidr>("2001:db8::/64", 1, "2001:db8::1", u64::MAX as u128 - 1);
+	test_nth::<IpCidr>("2001:db8::/64", 1, "2001:db8::1", u64::MAX as u128 - 1);
+	test_nth::<Ipv6Cidr>(
+		"::/0",
+		u128::MAX,
+		"ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff",
+		0,
+	);
+
+	let cidr = "2001:db8::/64".parse::<Ipv6Cidr>().unwrap();
+	assert_eq!(cidr.nth(1 << 64), None);
+	assert_eq!(cidr.nth_from_end(u128::MAX), None);
+}
+
+#[test]
+fn hosts_v4() {
+	let cidr = "192.0.2.0/24".parse::<Ipv4Cidr>().unwrap();
+	assert_eq!(cidr.network_address(), Ipv4Addr::new(192, 0, 2, 0));
+	assert_eq!(cidr.broadcast(), Some(Ipv4Addr::new(192, 0, 2, 255)));
+	assert_eq!(cidr.hosts().count(), 254);
+	assert_eq!(
+		cidr.hosts().addresses().next(),
+		Some(Ipv4Addr::new(192, 0, 2, 1))
+	);
+	assert_eq!(
+		cidr.hosts().addresses().next_back(),
+		Some(Ipv4Addr::new(192, 0, 2, 254))
+	);
+
+	let cidr = "192.0.2.0/30".parse::<Ipv4Cidr>().unwrap();
+	assert!(cidr
+		.hosts()
+		.addresses()
+		.eq([Ipv4Addr::new(192, 0, 2, 1), Ipv4Addr::new(192, 0, 2, 2)]));
+
+	// RFC 3021
+	let cidr = "192.0.2.0/31".parse::<Ipv4Cidr>().unwrap();
+	assert_eq!(cidr.broadcast(), None);
+	assert!(cidr.hosts().eq(cidr.iter()));
+
+	let cidr = "192.0.2.1/32".parse::<Ipv4Cidr>().unwrap();
+	assert_eq!(cidr.broadcast(), None);
+	assert!(cidr.hosts().eq(cidr.iter()));
+
+	let cidr = "192.0.2.0/24".parse::<IpCidr>().unwrap();
+	assert_eq!(cidr.hosts().count(), 254);
+	assert_eq!(
+		cidr.hosts().addresses().next(),
+		Some(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)))
+	);
+}
+
+#[test]
+fn hosts_v6() {
+	let cidr = "2001:db8::/120".parse::<Ipv6Cidr>().unwrap();
+	assert_eq!(cidr.hosts().count(), 255);
+	assert_eq!(
+		cidr.hosts().addresses().next(),
+		Some("2001:db8::1".parse().unwrap())
+	);
+	assert_eq!(
+		cidr.hosts().addresses().next_back(),
+		Some("2001:db8::ff".parse().unwrap())
+	);
+	assert_eq!(cidr.hosts_without_reserved_anycast().count(), 127);
+	assert_eq!(
+		cidr.hosts_without_reserved_anycast()
+			.next_back()
+			.map(|inet| inet.address()),
+		Some("2001:db8::7f".parse().unwrap())
+	);
+
+	let cidr = "2001:db8::/124".parse::<Ipv6Cidr>().unwrap();
+	assert!(cidr.hosts_without_reserved_anycast().eq(cidr.hosts()));
+	assert_eq!(cidr.hosts().count(), 15);
+
+	// RFC 6164
+	let cidr = "2001:db8::/127".parse::<Ipv6Cidr>().unwrap();
+	assert!(cidr.hosts().eq(cidr.iter()));
+
+	let cidr = "2001:db8::/64".parse::<Ipv6Cidr>().unwrap();
+	let mut hosts = cidr.hosts_without_reserved_anycast();
+	assert_eq!(
+		hosts.next().map(|inet| inet.address()),
+		Some("2001:db8::1".parse().unwrap())
+	);
+	assert_eq!(
+		hosts.next_back().map(|inet| inet.address()),
+		Some("2001:db8::ffff:ffff:ffff:ffff".parse().unwrap())
+	);
+
+	let cidr = "2001:db8::/120".parse::<IpCidr>().unwrap();
+	assert_eq!(cidr.hosts().count(), 255);
+}
+
+#[test]
+fn test_any_iter() {
+	let mut iter = AnyIpCidr::Any.iter();
+	assert_eq!(iter.next(), Some("0.0.0.0/0".parse().unwrap()));
+	assert_eq!(iter.next(), Some("0.0.0.1/0".parse().unwrap()));
+	assert_eq!(iter.size_hint(), (usize::MAX, None));
+
+	let mut iter = AnyIpCidr::Any.iter().skip(1 << 32);
+	assert_eq!(iter.next(), Some("::/0".parse().unwrap()));
+	assert_eq!(
+		AnyIpCidr::Any.iter().nth((1 << 32) - 1),
+		Some("255.255.255.255/0".parse().unwrap())
+	);
+	assert_eq!(
+		AnyIpCidr::Any.iter().last(),
+		Some("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/0".parse().unwrap())
+	);
+
+	let mut iter = "::/127".parse::<AnyIpCidr>().unwrap().iter();
+	assert_eq!(iter.size_hint(), (2, Some(2)));
+	assert_eq!(iter.nth_back(1), Some("::/127".parse().unwrap()));
+	assert_eq!(iter.next(), None);
+
+	let cidr = "192.0.2.0/30".parse::<AnyIpCidr>().unwrap();
+	assert!(cidr
+		.iter()
+		.eq("192.0.2.0/30".parse::<IpCidr>().unwrap().iter()));
+}
+
+#[test]
+fn test_network_like() {
+	fn allows<N: NetworkLike>(rule: N, addr: &str, net: &str) -> (bool, bool) {
+		(
+			rule.contains_address(&addr.parse().unwrap()),
+			rule.contains_network(&net.parse::<AnyIpCidr>().unwrap()),
+		)
+	}
+
+	let v4 = "10.0.0.0/8".parse::<Ipv4Cidr>().unwrap();
+	assert!(!v4.is_any());
+	assert_eq!(allows(v4, "10.1.2.3", "10.1.0.0/16"), (true, true));
+	assert_eq!(allows(v4, "11.0.0.1", "10.0.0.0/7"), (false, false));
+	assert_eq!(allows(v4, "::a00:1", "::a00:0/104"), (false, false));
+	assert_eq!(allows(v4, "10.0.0.1", "any"), (true, false));
+
+	let v6 = "2001:db8::/32".parse::<IpCidr>().unwrap();
+	assert_eq!(allows(v6, "2001:db8::1", "2001:db8:1::/48"), (true, true));
+	assert_eq!(allows(v6, "2001:db9::1", "10.0.0.0/8"), (false, false));
+
+	assert!(AnyIpCidr::Any.is_any());
+	assert_eq!(allows(AnyIpCidr::Any, "10.0.0.1", "any"), (true, true));
+	assert_eq!(allows(AnyIpCidr::Any, "::1", "::/0"), (true, true));
+	assert!(AnyIpCidr::Any.contains_network(&v4));
+	assert!(AnyIpCidr::from(v4).contains_network(&v4));
+	assert!(v4.contains_network(&AnyIpCidr::from(v4)));
+	assert!(!v4.contains_network(&AnyIpCidr::Any));
+	assert_eq!(v4.to_any_ip_cidr(), AnyIpCidr::V4(v4));
+}
+
+#[test]
+fn test_bits() {
+	let cidr = "192.0.2.0/24".parse::<Ipv4Cidr>().unwrap();
+	assert_eq!(cidr.to_bits(), (0xc000_0200, 24));
+	assert_eq!(<(u32, u8)>::from(cidr), (0xc000_0200, 24));
+	assert_eq!(Ipv4Cidr::from_bits(0xc000_0200, 24), Ok(cidr));
+	assert_eq!(Ipv4Cidr::try_from((0xc000_0200, 24)), Ok(cidr));
+	assert!(Ipv4Cidr::from_bits(0xc000_0201, 24).is_err());
+	assert!(Ipv4Cidr::from_bits(0, 33).is_err());
+
+	let cidr = "2001:db8::/32".parse::<Ipv6Cidr>().unwrap();
+	assert_eq!(cidr.to_bits(), (0x2001_0db8 << 96, 32));
+	assert_eq!(Ipv6Cidr::from_bits(0x2001_0db8 << 96, 32), Ok(cidr));
+
+	let cidr = "10.0.0.0/8".parse::<IpCidr>().unwrap();
+	assert_eq!(cidr.to_bits(), (0x0a00_0000, 8));
+	assert_eq!(
+		IpCidr::from_bits(crate::Family::Ipv4, 0x0a00_0000, 8),
+		Ok(cidr)
+	);
+	assert_eq!(
+		IpCidr::from_bits(crate::Family::Ipv4, 1 << 32, 0),
+		Err(crate::errors::NetworkParseError::AddressBitsOutOfRange)
+	);
+}
@@ -0,0 +1,511 @@
+// const parsers (`parse_const`) for networks and hosts
+//
+// Accept exactly the same inputs as the `FromStr` implementations: the
+// address parsers follow the rules of `core::net` (see
+// `core/src/net/parser.rs`), the network length parser those of
+// `u8::from_str`.
+
+use core::net::{
+	IpAddr,
+	Ipv4Addr,
+	Ipv6Addr,
+};
+
+use crate::{
+	errors::{
+		ConstParseError,
+		NetworkParseError,
+	},
+	internal_traits::PrivUnspecAddress,
+	AnyIpCidr,
+	AnyIpInet,
+	IpCidr,
+	IpInet,
+	Ipv4Cidr,
+	Ipv4Inet,
+	Ipv6Cidr,
+	Ipv6Inet,
+};
+
+const fn from_cidr_error(e: NetworkParseError) -> ConstParseError {
+	match e {
+		NetworkParseError::InvalidHostPart(_) => ConstParseError::InvalidHostPart,
+		NetworkParseError::NetworkLengthTooLongError(e) => {
+			ConstParseError::NetworkLengthTooLongError(e)
+		},
+		// not returned by constructors
+		NetworkParseError::AddrParseError(_)
+		| NetworkParseError::ExtraSeparator
+		| NetworkParseError::FamilyMismatch(_)
+		| NetworkParseError::AddressBitsOutOfRange => ConstParseError::AddrParseError,
+		NetworkParseError::NetworkLengthParseError(_) | NetworkParseError::EmptyNetworkLength => {
+			ConstParseError::NetworkLengthParseError
+		},
+	}
+}
+
+const fn digit(c: u8, radix: u32) -> Option<u32> {
+	let d = match c {
+		b'0'..=b'9' => c - b'0',
+		b'a'..=b'f' => c - b'a' + 10,
+		b'A'..=b'F' => c - b'A' + 10,
+		_ => return None,
+	};
+	if (d as u32) < radix {
+		Some(d as u32)
+	} else {
+		None
+	}
+}
+
+// read number with at most `max_digits` digits starting at `pos`; returns
+// value and position after the number
+const fn read_number(
+	s: &[u8],
+	pos: usize,
+	radix: u32,
+	max_digits: usize,
+	allow_zero_prefix: bool,
+) -> Option<(u32, usize)> {
+	let mut result: u32 = 0;
+	let mut p = pos;
+	while p < s.len() {
+		let d = match digit(s[p], radix) {
+			Some(d) => d,
+			None => break,
+		};
+		result = result * radix + d;
+		p += 1;
+		if p - pos > max_digits {
+			return None;
+		}
+	}
+	let digits = p - pos;
+	if digits == 0 || (!allow_zero_prefix && s[pos] == b'0' && digits > 1) {
+		None
+	} else {
+		Some((result, p))
+	}
+}
+
+// read IPv4 address starting at `pos` (not necessarily until end of input)
+const fn read_ipv4(s: &[u8], pos: usize) -> Option<(Ipv4Addr, usize)> {
+	let mut octets = [0u8; 4];
+	let mut p = pos;
+	let mut i = 0;
+	while i < 4 {
+		if i > 0 {
+			if p >= s.len() || s[p] != b'.' {
+				return None;
+			}
+			p += 1;
+		}
+		match read_number(s, p, 10, 3, false) {
+			Some((n, next)) if n < 256 => {
+				octets[i] = n as u8;
+				p = next;
+			},
+			_ => return None,
+		}
+		i += 1;
+	}
+	Some((Ipv4Addr::new(octets[0], octets[1], octets[2], octets[3]), p))
+}
+
+// read up to `limit` ':' separated groups (the last two groups can be an
+// embedded IPv4 address); returns number of groups read, whether an IPv4
+// address was embedded and the position after the last group
+const fn read_groups(
+	s: &[u8],
+	pos: usize,
+	groups: &mut [u16; 8],
+	limit: usize,
+) -> (usize, bool, usize) {
+	let mut p = pos;
+	let mut i = 0;
+	while i < limit {
+		let start = if i > 0 {
+			if p < s.len() && s[p] == b':' {
+				Some(p + 1)
+			} else {
+				None
+			}
+		} else {
+			Some(p)
+		};
+		let start = match start {
+			Some(start) => start,
+			None => return (i, false, p),
+		};
+		if i + 1 < limit {
+			if let Some((ipv4, next)) = read_ipv4(s, start) {
+				let [one, two, three, four] = ipv4.octets();
+				groups[i] = u16::from_be_bytes([one, two]);
+				groups[i + 1] = u16::from_be_bytes([three, four]);
+				return (i + 2, true, next);
+			}
+		}
+		match read_number(s, start, 16, 4, true) {
+			Some((group, next)) => {
+				groups[i] = group as u16;
+				p = next;
+			},
+			None => return (i, false, p),
+		}
+		i += 1;
+	}
+	(limit, false, p)
+}
+
+const fn read_ipv6(s: &[u8], pos: usize) -> Option<(Ipv6Addr, usize)> {
+	let mut head = [0u16; 8];
+	let (head_size, head_ipv4, p) = read_groups(s, pos, &mut head, 8);
+	if head_size == 8 {
+		return Some((ipv6_from_groups(head), p));
+	}
+	// IPv4 part is not allowed before `::`
+	if head_ipv4 {
+		return None;
+	}
+	if p + 1 >= s.len() || s[p] != b':' || s[p + 1] != b':' {
+		return None;
+	}
+	// `::` must represent at least one group of zeroes
+	let mut tail = [0u16; 8];
+	let limit = 8 - (head_size + 1);
+	let (tail_size, _, p) = read_groups(s, p + 2, &mut tail, limit);
+	let mut i = 0;
+	while i < tail_size {
+		head[8 - tail_size + i] = tail[i];
+		i += 1;
+	}
+	Some((ipv6_from_groups(head), p))
+}
+
+const fn ipv6_from_groups(groups: [u16; 8]) -> Ipv6Addr {
+	let mut native: u128 = 0;
+	let mut i = 0;
+	while i < 8 {
+		native = (native << 16) | groups[i] as u128;
+		i += 1;
+	}
+	<Ipv6Addr as PrivUnspecAddress>::_Tools::from_native(native)
+}
+
+pub(crate) const fn parse_ipv4_addr(s: &[u8]) -> Option<Ipv4Addr> {
+	match read_ipv4(s, 0) {
+		Some((addr, p)) if p == s.len() => Some(addr),
+		_ => None,
+	}
+}
+
+pub(crate) const fn parse_ipv6_addr(s: &[u8]) -> Option<Ipv6Addr> {
+	match read_ipv6(s, 0) {
+		Some((addr, p)) if p == s.len() => Some(addr),
+		_ => None,
+	}
+}
+
+pub(crate) const fn parse_ip_addr(s: &[u8]) -> Option<IpAddr> {
+	if let Some(addr) = parse_ipv4_addr(s) {
+		return Some(IpAddr::V4(addr));
+	}
+	match parse_ipv6_addr(s) {
+		Some(addr) => Some(IpAddr::V6(addr)),
+		None => None,
+	}
+}
+
+// like `u8::from_str`
+pub(crate) const fn parse_network_length(s: &[u8]) -> Option<u8> {
+	let mut p = 0;
+	if !s.is_empty() && s[0] == b'+' {
+		p = 1;
+	}
+	if p == s.len() {
+		return None;
+	}
+	let mut result: u8 = 0;
+	while p < s.len() {
+		let c = s[p];
+		if !c.is_ascii_digit() {
+			return None;
+		}
+		result = match result.checked_mul(10) {
+			Some(r) => match r.checked_add(c - b'0') {
+				Some(r) => r,
+				None => return None,
+			},
+			None => return None,
+		};
+		p += 1;
+	}
+	Some(result)
+}
+
+// split at the last '/'
+const fn split_network(s: &[u8]) -> (&[u8], Option<&[u8]>) {
+	let mut pos = s.len();
+	while pos > 0 {
+		pos -= 1;
+		if s[pos] == b'/' {
+			let (address, length) = s.split_at(pos);
+			return (address, Some(length.split_at(1).1));
+		}
+	}
+	(s, None)
+}
+
+macro_rules! impl_const_parse {
+	($n:ident : $parse_addr:ident : $error:path) => {
+		impl $n {
+			/// Parse from string in const context
+			///
+			/// Accepts the same input as the `FromStr` implementation, but
+			/// the returned error doesn't contain the details of address or
+			/// network length parse failures.
+			pub const fn parse_const(s: &str) -> Result<Self, ConstParseError> {
+				let (address, length) = split_network(s.as_bytes());
+				let address = match $parse_addr(address) {
+					Some(address) => address,
+					None => return Err(ConstParseError::AddrParseError),
+				};
+				let length = match length {
+					None => return Ok(Self::new_host(address)),
+					Some(length) => match parse_network_length(length) {
+						Some(length) => length,
+						None => return Err(ConstParseError::NetworkLengthParseError),
+					},
+				};
+				match Self::new(address, length) {
+					Ok(value) => Ok(value),
+					Err(e) => Err($error(e)),
+				}
+			}
+		}
+	};
+}
+
+impl_const_parse! {Ipv4Cidr : parse_ipv4_addr : from_cidr_error}
+impl_const_parse! {Ipv6Cidr : parse_ipv6_addr : from_cidr_error}
+impl_const_parse! {IpCidr : parse_ip_addr : from_cidr_error}
+impl_const_parse! {Ipv4Inet : parse_ipv4_addr : ConstParseError::NetworkLengthTooLongError}
+impl_const_parse! {Ipv6Inet : parse_ipv6_addr : ConstParseError::NetworkLengthTooLongError}
+impl_const_parse! {IpInet : parse_ip_addr : ConstParseError::NetworkLengthTooLongError}
+
+impl AnyIpCidr {
+	/// Parse from string in const context
+	///
+	/// Accepts the same input as the `FromStr` implementation (including
+	/// `"any"`), but the returned error doesn't contain the details of
+	/// address or network length parse failures.
+	pub const fn parse_const(s: &str) -> Result<Self, ConstParseError> {
+		if let b"any" = s.as_bytes() {
+			return Ok(Self::Any);
+		}
+		match IpCidr::parse_const(s) {
+			Ok(IpCidr::V4(cidr)) => Ok(Self::V4(cidr)),
+			Ok(IpCidr::V6(cidr)) => Ok(Self::V6(cidr)),
+			Err(e) => Err(e),
+		}
+	}
+}
+
+impl AnyIpInet {
+	/// Parse from string in const context
+	///
+	/// Accepts the same input as the `FromStr` implementation (including
+	/// `"any"`), but the returned error doesn't contain the details of
+	/// address or network length parse failures.
+	pub const fn parse_const(s: &str) -> Result<Self, ConstParseError> {
+		if let b"any" = s.as_bytes() {
+			return Ok(Self::Any);
+		}
+		match IpInet::parse_const(s) {
+			Ok(IpInet::V4(inet)) => Ok(Self::V4(inet)),
+			Ok(IpInet::V6(inet)) => Ok(Self::V6(inet)),
+			Err(e) => Err(e),
+		}
+	}
+}
+
+macro_rules! impl_or_panic {
+	($fn:ident : $n:ident) => {
+		#[doc(hidden)]
+		pub const fn $fn(s: &str) -> $n {
+			match $n::parse_const(s) {
+				Ok(value) => value,
+				Err(e) => panic!("{}", e.message()),
+			}
+		}
+	};
+}
+
+// used by the literal macros; not part of the public API
+#[doc(hidden)]
+pub mod __private {
+	use super::*;
+
+	impl_or_panic! {ipv4_cidr : Ipv4Cidr}
+	impl_or_panic! {ipv6_cidr : Ipv6Cidr}
+	impl_or_panic! {ip_cidr : IpCidr}
+	impl_or_panic! {ipv4_inet : Ipv4Inet}
+	impl_or_panic! {ipv6_inet : Ipv6Inet}
+	impl_or_panic! {ip_inet : IpInet}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::str::FromStr;
+
+	use super::*;
+
+	const INPUTS: &[&str] = &[
+		"",
+		"/",
+		"/8",
+		"0.0.0.0",
+		"0.0.0.0/0",
+		"10.0.0.0/8",
+		"10.0.0.1/8",
+		"10.0.0.0/+8",
+		"10.0.0.0/08",
+		"10.0.0.0/-8",
+		"10.0.0.0/+",
+		"10.0.0.0/",
+		"10.0.0.0/33",
+		"10.0.0.0/256",
+		"10.0.0.0/8/8",
+		"10.0.0/8",
+		"10.0.0.0.0/8",
+		"010.0.0.0/8",
+		"1.2.3.4",
+		"1.2.3.04",
+		"1.2.3.256",
+		"1.2.3.255/32",
+		"1.2.3.4/32 ",
+		" 1.2.3.4",
+		"1.2.3.",
+		"1..2.3",
+		"192.0.2.1/24",
+		"::",
+		"::/0",
+		"::/128",
+		"::1",
+		"::1/129",
+		":::",
+		"::1::",
+		"1::",
+		"1:",
+		":1",
+		"1::2",
+		"1:2:3:4:5:6:7:8",
+		"1:2:3:4:5:6:7:8/128",
+		"1:2:3:4:5:6:7:8:9",
+		"1:2:3:4:5:6:7::",
+		"1:2:3:4:5:6:7:8::",
+		"::1:2:3:4:5:6:7",
+		"::1:2:3:4:5:6:7:8",
+		"1:2:3:4::5:6:7:8",
+		"2001:db8::/32",
+		"2001:db8::1/32",
+		"2001:DB8::/32",
+		"2001:0db8::/32",
+		"2001:00db8::/32",
+		"2001:db8:0:0:0:0:0:0/32",
+		"::ffff:192.0.2.1",
+		"::ffff:192.0.2.1/120",
+		"::ffff:192.0.2.0/120",
+		"::192.0.2.1",
+		"1:2:3:4:5:6:192.0.2.1",
+		"1:2:3:4:5:6:7:192.0.2.1",
+		"1:2:3:4:5::192.0.2.1",
+		"1:2:3:4:5:6::192.0.2.1",
+		"192.0.2.1::",
+		"::ffff:192.0.2",
+		"::ffff:192.0.2.1.1",
+		"::ffff:1.2.3.04",
+		"fe80::1%eth0",
+		"g::",
+		"any",
+		"any/0",
+		"Any",
+	];
+
+	#[test]
+	fn same_as_from_str() {
+		fn check<T: FromStr + PartialEq + core::fmt::Debug>(
+			s: &str,
+			parse_const: fn(&str) -> Result<T, ConstParseError>,
+		) {
+			match (parse_const(s), T::from_str(s)) {
+				(Ok(a), Ok(b)) => assert_eq!(a, b, "{:?}", s),
+				(Err(_), Err(_)) => (),
+				(a, b) => panic!("{:?}: const parser: {:?}, FromStr: {:?}", s, a, b.is_ok()),
+			}
+		}
+
+		for s in INPUTS {
+			check::<Ipv4Cidr>(s, Ipv4Cidr::parse_const);
+			check::<Ipv6Cidr>(s, Ipv6Cidr::parse_const);
+			check::<IpCidr>(s, IpCidr::parse_const);
+			check::<Ipv4Inet>(s, Ipv4Inet::parse_const);
+			check::<Ipv6Inet>(s, Ipv6Inet::parse_const);
+			check::<IpInet>(s, IpInet::parse_const);
+			check::<AnyIpCidr>(s, AnyIpCidr::parse_const);
+			check::<AnyIpInet>(s, AnyIpInet::parse_const);
+		}
+	}
+
+	#[test]
+	fn errors() {
+		const INVALID: Result<Ipv6Cidr, ConstParseError> = Ipv6Cidr::parse_const("2001:db8::1/32");
+		assert_eq!(INVALID, Err(ConstParseError::InvalidHostPart));
+		assert_eq!(
+			Ipv4Cidr::parse_const("10.0.0.1/8"),
+			Err(ConstParseError::InvalidHostPart)
+		);
+		assert_eq!(
+			Ipv4Cidr::parse_const("10.0.0.256/8"),
+			Err(ConstParseError::AddrParseError)
+		);
+		assert_eq!(
+			Ipv4Cidr::parse_const("10.0.0.0/x"),
+			Err(ConstParseError::NetworkLengthParseError)
+		);
+		assert!(matches!(
+			Ipv4Inet::parse_const("10.0.0.0/33"),
+			Err(ConstParseError::NetworkLengthTooLongError(_))
+		));
+		assert_eq!(
+			AnyIpInet::parse_const("any/0"),
+			Err(ConstParseError::AddrParseError)
+		);
+	}
+
+	#[test]
+	fn macros() {
+		const CIDR: Ipv4Cidr = crate::ipv4_cidr!("10.0.0.0/8");
+		assert_eq!(CIDR, "10.0.0.0/8".parse().unwrap());
+		assert_eq!(
+			crate::ipv6_cidr!("2001:db8::/32"),
+			"2001:db8::/32".parse::<Ipv6Cidr>().unwrap()
+		);
+		assert_eq!(
+			crate::cidr!("::ffff:192.0.2.0/120"),
+			"::ffff:192.0.2.0/120".parse::<IpCidr>().unwrap()
+		);
+		assert_eq!(
+			crate::ipv4_inet!("192.0.2.1/24"),
+			"192.0.2.1/24".parse::<Ipv4Inet>().unwrap()
+		);
+		assert_eq!(
+			crate::ipv6_inet!("2001:db8::1"),
+			"2001:db8::1".parse::<Ipv6Inet>().unwrap()
+		);
+		assert_eq!(
+			crate::inet!("192.0.2.1"),
+			"192.0.2.1".parse::<IpInet>().unwrap()
+		);
+	}
+}
@@ -62,7 +62,9 @@ impl<const SIZE: usize> fmt::Write for DisplayBuffer<SIZE> {
 
 		if let Some(buf) = self.buf.get_mut(self.len..(self.len + bytes.len())) {
 			#[cfg(not(feature = "no_unsafe"))]
-			buf.copy_from_slice(unsafe { core::mem::transmute(bytes) });
+			buf.copy_from_slice(unsafe {
+				core::mem::transmute::<&[u8], &[MaybeUninit<u8>]>(bytes)
+			});
 			#[cfg(feature = "no_unsafe")]
 			buf.copy_from_slice(bytes);
 			self.len += bytes.len();
@@ -76,7 +78,13 @@ impl<const SIZE: usize> fmt::Write for DisplayBuffer<SIZE> {
 const LONGEST_IPV6_CIDR: &str = "V6(ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128)";
 
 pub fn format_cidr_fixed(args: fmt::Arguments<'_>) -> impl core::ops::Deref<Target = str> {
-	let mut result = DisplayBuffer::<{ LONGEST_IPV6_CIDR.len() }>::new();
+	format_fixed::<{ LONGEST_IPV6_CIDR.len() }>(args)
+}
+
+pub fn format_fixed<const SIZE: usize>(
+	args: fmt::Arguments<'_>,
+) -> impl core::ops::Deref<Target = str> {
+	let mut result = DisplayBuffer::<SIZE>::new();
 	fmt::write(&mut result, args).expect("format failed");
 	result
 }
@@ -0,0 +1,1032 @@
+//! Various error types returned by function in this crate
+
+use core::{
+	error::Error,
+	fmt,
+	net::{
+		AddrParseError,
+		IpAddr,
+	},
+	num::ParseIntError,
+	ops::Range,
+};
+
+use crate::{
+	Family,
+	IpCidr,
+	IpInet,
+};
+
+mod serde;
+
+/// Category of an error, shared by all error types in this module
+///
+/// Variants not listed for an error type are never returned by its
+/// `kind` method; new variants might be added in the future.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[non_exhaustive]
+pub enum ErrorKind {
+	/// The host part of an address wasn't zero but should have been
+	InvalidHostPart,
+	/// An address couldn't be parsed
+	InvalidAddress,
+	/// A network length couldn't be parsed (or was missing)
+	InvalidNetworkLength,
+	/// A network length was too long for the address family
+	NetworkLengthTooLong,
+	/// The input contained more than one `'/'` separator
+	ExtraSeparator,
+	/// An address was not of the expected family
+	FamilyMismatch,
+	/// Addresses were not in the same network
+	NotInSharedNetwork,
+	/// The input ended early
+	UnexpectedEnd,
+	/// The input contained more data than expected
+	TrailingData,
+	/// The output buffer was too small
+	BufferTooSmall,
+	/// A tag in a binary representation was invalid
+	InvalidTag,
+	/// Padding in a binary representation wasn't zero
+	NonZeroPadding,
+	/// No free space left for an allocation
+	Exhausted,
+	/// A network or address was outside of the managed space
+	NotInPool,
+	/// A network or address overlapped one already in use
+	Overlapping,
+	/// A network or address to release wasn't allocated
+	NotAllocated,
+	/// A keyword or number in a text format was invalid
+	InvalidSyntax,
+	/// A numeric address didn't fit into an address of the family
+	AddressOutOfRange,
+}
+
+/// Error returned when the network length was longer than the address
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct NetworkLengthTooLongError(usize, Family);
+
+impl NetworkLengthTooLongError {
+	pub(crate) const fn new(len: usize, family: Family) -> Self {
+		NetworkLengthTooLongError(len, family)
+	}
+
+	/// category of the error: [`ErrorKind::NetworkLengthTooLong`]
+	pub const fn kind(&self) -> ErrorKind {
+		ErrorKind::NetworkLengthTooLong
+	}
+
+	/// the rejected network length
+	pub const fn network_length(&self) -> usize {
+		self.0
+	}
+
+	/// the address family the network length was too long for
+	pub const fn family(&self) -> Family {
+		self.1
+	}
+}
+
+impl fmt::Display for NetworkLengthTooLongError {
+	fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			w,
+			"Network length {} is too long for {:?} (maximum: {})",
+			self.0,
+			self.1,
+			self.1.len()
+		)
+	}
+}
+
+impl Error for NetworkLengthTooLongError {
+	fn description(&self) -> &str {
+		"network length too long"
+	}
+}
+
+impl fmt::Debug for NetworkLengthTooLongError {
+	fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
+		w.debug_struct("NetworkLengthTooLongError")
+			.field("network_length", &self.0)
+			.field("family", &self.1)
+			.finish()
+	}
+}
+
+/// Error details when the host part of an address wasn't zero
+///
+/// Contains the rejected address and network length; the [`Display`]
+/// output shows the non-zero host bits and suggests the network
+/// containing the address.
+///
+/// [`Display`]: fmt::Display
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidHostPartError(IpInet);
+
+impl InvalidHostPartError {
+	pub(crate) const fn new(inet: IpInet) -> Self {
+		InvalidHostPartError(inet)
+	}
+
+	/// category of the error: [`ErrorKind::InvalidHostPart`]
+	pub const fn kind(&self) -> ErrorKind {
+		ErrorKind::InvalidHostPart
+	}
+
+	/// the rejected address with its network length
+	pub const fn inet(&self) -> IpInet {
+		self.0
+	}
+
+	/// the network containing the rejected address (probably what was
+	/// meant)
+	pub const fn network(&self) -> IpCidr {
+		self.0.network()
+	}
+
+	/// the host part of the rejected address (the bits that should have
+	/// been zero)
+	pub const fn host_part(&self) -> IpAddr {
+		match self.0 {
+			IpInet::V4(inet) => IpAddr::V4(inet._host_part_address()),
+			IpInet::V6(inet) => IpAddr::V6(inet._host_part_address()),
+		}
+	}
+}
+
+impl fmt::Display for InvalidHostPartError {
+	fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			w,
+			"{:#} has host bits {}; did you mean {:#}?",
+			self.0,
+			self.host_part(),
+			self.network()
+		)
+	}
+}
+
+impl Error for InvalidHostPartError {
+	fn description(&self) -> &str {
+		"host part not zero"
+	}
+}
+
+impl fmt::Debug for InvalidHostPartError {
+	fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
+		w.debug_struct("InvalidHostPartError")
+			.field("inet", &self.0)
+			.finish()
+	}
+}
+
+/// Error type returned when parsing IP networks
+///
+/// New variants might be added in the future.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum NetworkParseError {
+	/// The host part wasn't zero but should have been. The [`Cidr`] types
+	/// require that you use the first address in the network (and the
+	/// network length) to represent the address, but it wasn't the
+	/// first address.
+	///
+	/// [`Cidr`]: crate::Cidr
+	InvalidHostPart(InvalidHostPartError),
+	/// Failed to parse the address
+	AddrParseError(AddrParseError),
+	/// Failed to parse the network length
+	NetworkLengthParseError(ParseIntError),
+	/// The network length was not valid (but was successfully parsed)
+	NetworkLengthTooLongError(NetworkLengthTooLongError),
+	/// The network length after the `'/'` separator was empty
+	EmptyNetworkLength,
+	/// The input contained more than one `'/'` separator
+	ExtraSeparator,
+	/// The address was valid, but not of the expected family; contains
+	/// the family of the address found
+	FamilyMismatch(Family),
+	/// The numeric representation of an address didn't fit into an
+	/// address of the requested family (only returned by the `from_bits`
+	/// constructors)
+	AddressBitsOutOfRange,
+}
+
+impl NetworkParseError {
+	/// category of the error
+	pub const fn kind(&self) -> ErrorKind {
+		match self {
+			Self::InvalidHostPart(_) => ErrorKind::InvalidHostPart,
+			Self::AddrParseError(_) => ErrorKind::InvalidAddress,
+			Self::NetworkLengthParseError(_) => ErrorKind::InvalidNetworkLength,
+			Self::NetworkLengthTooLongError(_) => ErrorKind::NetworkLengthTooLong,
+			Self::EmptyNetworkLength => ErrorKind::InvalidNetworkLength,
+			Self::ExtraSeparator => ErrorKind::ExtraSeparator,
+			Self::FamilyMismatch(_) => ErrorKind::FamilyMismatch,
+			Self::AddressBitsOutOfRange => ErrorKind::AddressOutOfRange,
+		}
+	}
+
+	/// Byte range of the part of `input` that failed to parse
+	///
+	/// `input` must be the string that was passed to the parser that
+	/// returned this error; parsers split the input at the last `'/'`
+	/// into address and network length.  Returns `None` if the error
+	/// doesn't refer to a part of the input (e.g. when the error wasn't
+	/// returned by a parser).
+	///
+	/// ```
+	/// # use cidr::Ipv4Cidr;
+	/// let input = "10.0.0.300/8";
+	/// let err = input.parse::<Ipv4Cidr>().unwrap_err();
+	/// assert_eq!(err.span(input), Some(0..10));
+	/// assert_eq!(&input[err.span(input).unwrap()], "10.0.0.300");
+	/// ```
+	pub fn span(&self, input: &str) -> Option<Range<usize>> {
+		let separator = input.rfind('/');
+		let address = 0..separator.unwrap_or(input.len());
+		let length = separator.map(|pos| pos + 1..input.len());
+		match self {
+			Self::InvalidHostPart(_) | Self::AddrParseError(_) | Self::FamilyMismatch(_) => {
+				Some(address)
+			},
+			Self::NetworkLengthParseError(_)
+			| Self::NetworkLengthTooLongError(_)
+			| Self::EmptyNetworkLength => length,
+			Self::ExtraSeparator => input
+				.find('/')
+				.filter(|&pos| Some(pos) != separator)
+				.map(|pos| pos..pos + 1),
+			Self::AddressBitsOutOfRange => None,
+		}
+	}
+
+	// replace address parse errors with `FamilyMismatch` if the address
+	// is valid for the other family
+	pub(crate) fn check_family(self, input: &str, expected: Family) -> Self {
+		if !matches!(self, Self::AddrParseError(_)) {
+			return self;
+		}
+		let address = match input.rfind('/') {
+			Some(pos) => &input[..pos],
+			None => input,
+		};
+		match (address.parse::<IpAddr>(), expected) {
+			(Ok(IpAddr::V6(_)), Family::Ipv4) => Self::FamilyMismatch(Family::Ipv6),
+			(Ok(IpAddr::V4(_)), Family::Ipv6) => Self::FamilyMismatch(Family::Ipv4),
+			_ => self,
+		}
+	}
+}
+
+impl fmt::Display for NetworkParseError {
+	fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::InvalidHostPart(e) => write!(w, "host part of address was not zero: {}", e),
+			Self::AddrParseError(e) => {
+				write!(w, "couldn't parse address in network: {}", e)
+			},
+			Self::NetworkLengthParseError(e) => {
+				write!(w, "couldn't parse length in network: {}", e)
+			},
+			Self::NetworkLengthTooLongError(e) => {
+				write!(w, "invalid length for network: {}", e)
+			},
+			Self::EmptyNetworkLength => write!(w, "missing length after '/' in network"),
+			Self::ExtraSeparator => write!(w, "more than one '/' in network"),
+			Self::FamilyMismatch(Family::Ipv4) => {
+				write!(w, "expected IPv6 address in network, found IPv4 address")
+			},
+			Self::FamilyMismatch(Family::Ipv6) => {
+				write!(w, "expected IPv4 address in network, found IPv6 address")
+			},
+			Self::AddressBitsOutOfRange => {
+				write!(w, "numeric address out of range for address family")
+			},
+		}
+	}
+}
+
+impl Error for NetworkParseError {
+	fn description(&self) -> &str {
+		"network parse error"
+	}
+
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::InvalidHostPart(e) => Some(e),
+			Self::AddrParseError(e) => Some(e),
+			Self::NetworkLengthParseError(e) => Some(e),
+			Self::NetworkLengthTooLongError(e) => Some(e),
+			Self::EmptyNetworkLength => None,
+			Self::ExtraSeparator => None,
+			Self::FamilyMismatch(_) => None,
+			Self::AddressBitsOutOfRange => None,
+		}
+	}
+}
+
+impl From<AddrParseError> for NetworkParseError {
+	fn from(e: AddrParseError) -> Self {
+		NetworkParseError::AddrParseError(e)
+	}
+}
+
+impl From<NetworkLengthTooLongError> for NetworkParseError {
+	fn from(e: NetworkLengthTooLongError) -> Self {
+		NetworkParseError::NetworkLengthTooLongError(e)
+	}
+}
+
+impl From<InvalidHostPartError> for NetworkParseError {
+	fn from(e: InvalidHostPartError) -> Self {
+		NetworkParseError::InvalidHostPart(e)
+	}
+}
+
+/// Error type returned by the `parse_const` functions
+///
+/// Like [`NetworkParseError`], but the details of address and network
+/// length parse failures aren't available (the error types from `core`
+/// can't be created in const functions).
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ConstParseError {
+	/// The host part wasn't zero but should have been (see
+	/// [`NetworkParseError::InvalidHostPart`])
+	InvalidHostPart,
+	/// Failed to parse the address
+	AddrParseError,
+	/// Failed to parse the network length
+	NetworkLengthParseError,
+	/// The network length was not valid (but was successfully parsed)
+	NetworkLengthTooLongError(NetworkLengthTooLongError),
+}
+
+impl ConstParseError {
+	/// category of the error
+	pub const fn kind(&self) -> ErrorKind {
+		match self {
+			Self::InvalidHostPart => ErrorKind::InvalidHostPart,
+			Self::AddrParseError => ErrorKind::InvalidAddress,
+			Self::NetworkLengthParseError => ErrorKind::InvalidNetworkLength,
+			Self::NetworkLengthTooLongError(_) => ErrorKind::NetworkLengthTooLong,
+		}
+	}
+
+	// message without details, usable in const panics
+	pub(crate) const fn message(&self) -> &'static str {
+		match self {
+			Self::InvalidHostPart => "host part of address was not zero",
+			Self::AddrParseError => "couldn't parse address in network",
+			Self::NetworkLengthParseError => "couldn't parse length in network",
+			Self::NetworkLengthTooLongError(_) => "invalid length for network",
+		}
+	}
+}
+
+impl fmt::Display for ConstParseError {
+	fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NetworkLengthTooLongError(e) => {
+				write!(w, "invalid length for network: {}", e)
+			},
+			_ => w.write_str(self.message()),
+		}
+	}
+}
+
+impl Error for ConstParseError {
+	fn description(&self) -> &str {
+		"network parse error"
+	}
+
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::NetworkLengthTooLongError(e) => Some(e),
+			_ => None,
+		}
+	}
+}
+
+impl From<NetworkLengthTooLongError> for ConstParseError {
+	fn from(e: NetworkLengthTooLongError) -> Self {
+		ConstParseError::NetworkLengthTooLongError(e)
+	}
+}
+
+/// Error type returned when creating [`Inet`] pair
+///
+/// [`Inet`]: crate::Inet
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum InetTupleError {
+	/// The given addresses are not in the same network
+	NotInSharedNetwork,
+	/// The network length was not valid (but was successfully parsed)
+	NetworkLengthTooLongError(NetworkLengthTooLongError),
+}
+
+impl InetTupleError {
+	/// category of the error
+	pub const fn kind(&self) -> ErrorKind {
+		match self {
+			Self::NotInSharedNetwork => ErrorKind::NotInSharedNetwork,
+			Self::NetworkLengthTooLongError(_) => ErrorKind::NetworkLengthTooLong,
+		}
+	}
+}
+
+impl fmt::Display for InetTupleError {
+	fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NotInSharedNetwork => write!(w, "addresses not in shared network"),
+			Self::NetworkLengthTooLongError(e) => {
+				write!(w, "invalid length for network: {}", e)
+			},
+		}
+	}
+}
+
+impl Error for InetTupleError {
+	fn description(&self) -> &str {
+		"inet tuple error"
+	}
+
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::NotInSharedNetwork => None,
+			Self::NetworkLengthTooLongError(e) => Some(e),
+		}
+	}
+}
+
+impl From<NetworkLengthTooLongError> for InetTupleError {
+	fn from(e: NetworkLengthTooLongError) -> Self {
+		InetTupleError::NetworkLengthTooLongError(e)
+	}
+}
+
+/// Error type returned when decoding prefixes in the BGP NLRI encoding
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum NlriDecodeError {
+	/// The input ended before the prefix was complete
+	UnexpectedEnd,
+	/// The input contained more data after the prefix
+	TrailingData,
+	/// The host part wasn't zero
+	InvalidHostPart,
+	/// The encoded network length was too long for the address family
+	NetworkLengthTooLongError(NetworkLengthTooLongError),
+}
+
+impl NlriDecodeError {
+	/// category of the error
+	pub const fn kind(&self) -> ErrorKind {
+		match self {
+			Self::UnexpectedEnd => ErrorKind::UnexpectedEnd,
+			Self::TrailingData => ErrorKind::TrailingData,
+			Self::InvalidHostPart => ErrorKind::InvalidHostPart,
+			Self::NetworkLengthTooLongError(_) => ErrorKind::NetworkLengthTooLong,
+		}
+	}
+}
+
+impl fmt::Display for NlriDecodeError {
+	fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::UnexpectedEnd => write!(w, "unexpected end of NLRI prefix"),
+			Self::TrailingData => write!(w, "trailing data after NLRI prefix"),
+			Self::InvalidHostPart => write!(w, "host part of NLRI prefix was not zero"),
+			Self::NetworkLengthTooLongError(e) => {
+				write!(w, "invalid length for NLRI prefix: {}", e)
+			},
+		}
+	}
+}
+
+impl Error for NlriDecodeError {
+	fn description(&self) -> &str {
+		"NLRI decode error"
+	}
+
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::UnexpectedEnd => None,
+			Self::TrailingData => None,
+			Self::InvalidHostPart => None,
+			Self::NetworkLengthTooLongError(e) => Some(e),
+		}
+	}
+}
+
+impl From<NetworkLengthTooLongError> for NlriDecodeError {
+	fn from(e: NetworkLengthTooLongError) -> Self {
+		NlriDecodeError::NetworkLengthTooLongError(e)
+	}
+}
+
+/// Error returned when the output buffer is too small to encode prefixes
+/// in the BGP NLRI encoding
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct NlriBufferTooSmallError(());
+
+impl NlriBufferTooSmallError {
+	pub(crate) const fn new() -> Self {
+		NlriBufferTooSmallError(())
+	}
+
+	/// category of the error: [`ErrorKind::BufferTooSmall`]
+	pub const fn kind(&self) -> ErrorKind {
+		ErrorKind::BufferTooSmall
+	}
+}
+
+impl fmt::Display for NlriBufferTooSmallError {
+	fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(w, "buffer too small for NLRI prefix")
+	}
+}
+
+impl Error for NlriBufferTooSmallError {
+	fn description(&self) -> &str {
+		"NLRI buffer too small"
+	}
+}
+
+impl fmt::Debug for NlriBufferTooSmallError {
+	fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
+		w.debug_struct("NlriBufferTooSmallError").finish()
+	}
+}
+
+/// Error type returned when decoding the fixed-size byte representation
+/// (`from_bytes`) of networks and hosts
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum FromBytesError {
+	/// The tag (first byte) doesn't represent a valid family and network
+	/// length for the type
+	InvalidTag(u8),
+	/// Bytes following an IPv4 address in a representation sized for IPv6
+	/// (or following the tag of `any`) were not zero
+	NonZeroPadding,
+	/// The host part wasn't zero
+	InvalidHostPart,
+}
+
+impl FromBytesError {
+	/// category of the error
+	pub const fn kind(&self) -> ErrorKind {
+		match self {
+			Self::InvalidTag(_) => ErrorKind::InvalidTag,
+			Self::NonZeroPadding => ErrorKind::NonZeroPadding,
+			Self::InvalidHostPart => ErrorKind::InvalidHostPart,
+		}
+	}
+}
+
+impl fmt::Display for FromBytesError {
+	fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::InvalidTag(tag) => write!(w, "invalid tag in byte representation: {:#04x}", tag),
+			Self::NonZeroPadding => write!(w, "padding in byte representation was not zero"),
+			Self::InvalidHostPart => write!(w, "host part of address was not zero"),
+		}
+	}
+}
+
+impl Error for FromBytesError {
+	fn description(&self) -> &str {
+		"byte representation decode error"
+	}
+}
+
+/// Error type returned by the allocators in the [`ipam`](crate::ipam)
+/// module
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum AllocationError {
+	/// No free space left for the requested allocation
+	Exhausted,
+	/// The requested network or address is not within a pool
+	NotInPool,
+	/// The requested network or address overlaps one already allocated
+	/// (or an existing pool)
+	Overlapping,
+	/// The network or address to release wasn't allocated
+	NotAllocated,
+	/// The requested network length was too long for the address family
+	NetworkLengthTooLongError(NetworkLengthTooLongError),
+}
+
+#[cfg(feature = "alloc")]
+impl AllocationError {
+	/// category of the error
+	pub const fn kind(&self) -> ErrorKind {
+		match self {
+			Self::Exhausted => ErrorKind::Exhausted,
+			Self::NotInPool => ErrorKind::NotInPool,
+			Self::Overlapping => ErrorKind::Overlapping,
+			Self::NotAllocated => ErrorKind::NotAllocated,
+			Self::NetworkLengthTooLongError(_) => ErrorKind::NetworkLengthTooLong,
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for AllocationError {
+	fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Exhausted => write!(w, "no free space left"),
+			Self::NotInPool => write!(w, "not within a pool"),
+			Self::Overlapping => write!(w, "overlaps an allocation"),
+			Self::NotAllocated => write!(w, "not allocated"),
+			Self::NetworkLengthTooLongError(e) => {
+				write!(w, "invalid length for network: {}", e)
+			},
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl Error for AllocationError {
+	fn description(&self) -> &str {
+		"allocation error"
+	}
+
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::NetworkLengthTooLongError(e) => Some(e),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl From<NetworkLengthTooLongError> for AllocationError {
+	fn from(e: NetworkLengthTooLongError) -> Self {
+		AllocationError::NetworkLengthTooLongError(e)
+	}
+}
+
+/// Error type returned by [`plan_subnets`](crate::ipam::plan_subnets)
+///
+/// Requirements are identified by their index in the list passed.
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum PlanError {
+	/// The requirement didn't fit into the remaining space of the parent
+	/// network
+	DoesNotFit {
+		/// index of the requirement
+		requirement: usize,
+		/// number of addresses missing to fit all requirements
+		/// (`u128::MAX` if at least that many are missing)
+		missing: u128,
+	},
+	/// The network length of the requirement was too long for the address
+	/// family
+	NetworkLengthTooLong {
+		/// index of the requirement
+		requirement: usize,
+		/// the underlying error
+		error: NetworkLengthTooLongError,
+	},
+}
+
+#[cfg(feature = "alloc")]
+impl PlanError {
+	/// category of the error
+	pub const fn kind(&self) -> ErrorKind {
+		match self {
+			Self::DoesNotFit { .. } => ErrorKind::Exhausted,
+			Self::NetworkLengthTooLong { .. } => ErrorKind::NetworkLengthTooLong,
+		}
+	}
+
+	/// index of the requirement that failed
+	pub const fn requirement(&self) -> usize {
+		match *self {
+			Self::DoesNotFit { requirement, .. } => requirement,
+			Self::NetworkLengthTooLong { requirement, .. } => requirement,
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for PlanError {
+	fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::DoesNotFit {
+				requirement,
+				missing,
+			} => write!(
+				w,
+				"requirement #{} doesn't fit: {} addresses missing",
+				requirement, missing
+			),
+			Self::NetworkLengthTooLong { requirement, error } => {
+				write!(
+					w,
+					"invalid length for requirement #{}: {}",
+					requirement, error
+				)
+			},
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl Error for PlanError {
+	fn description(&self) -> &str {
+		"subnet plan error"
+	}
+
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::NetworkLengthTooLong { error, .. } => Some(error),
+			_ => None,
+		}
+	}
+}
+
+/// Error type returned when parsing a
+/// [`PrefixFilter`](crate::prefix_filter::PrefixFilter) or one of its
+/// entries
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PrefixFilterParseError {
+	line: usize,
+	reason: PrefixFilterParseReason,
+}
+
+// only constructed by the parsers in `prefix_filter`
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) enum PrefixFilterParseReason {
+	UnexpectedEnd,
+	TrailingData,
+	InvalidSyntax,
+	InvalidLengthRange,
+	NetworkParseError(NetworkParseError),
+}
+
+#[cfg(feature = "alloc")]
+impl PrefixFilterParseError {
+	pub(crate) const fn new(line: usize, reason: PrefixFilterParseReason) -> Self {
+		Self { line, reason }
+	}
+
+	/// category of the error
+	pub const fn kind(&self) -> ErrorKind {
+		match &self.reason {
+			PrefixFilterParseReason::UnexpectedEnd => ErrorKind::UnexpectedEnd,
+			PrefixFilterParseReason::TrailingData => ErrorKind::TrailingData,
+			PrefixFilterParseReason::InvalidSyntax => ErrorKind::InvalidSyntax,
+			PrefixFilterParseReason::InvalidLengthRange => ErrorKind::InvalidNetworkLength,
+			PrefixFilterParseReason::NetworkParseError(e) => e.kind(),
+		}
+	}
+
+	/// line (starting at 1) the error occurred in
+	pub const fn line(&self) -> usize {
+		self.line
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for PrefixFilterParseError {
+	fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(w, "line {}: ", self.line)?;
+		match &self.reason {
+			PrefixFilterParseReason::UnexpectedEnd => write!(w, "unexpected end of entry"),
+			PrefixFilterParseReason::TrailingData => write!(w, "unexpected data after entry"),
+			PrefixFilterParseReason::InvalidSyntax => write!(w, "invalid keyword or number"),
+			PrefixFilterParseReason::InvalidLengthRange => {
+				write!(w, "invalid network length range")
+			},
+			PrefixFilterParseReason::NetworkParseError(e) => fmt::Display::fmt(e, w),
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl Error for PrefixFilterParseError {
+	fn description(&self) -> &str {
+		"prefix filter parse error"
+	}
+
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match &self.reason {
+			PrefixFilterParseReason::NetworkParseError(e) => Some(e),
+			_ => None,
+		}
+	}
+}
+
+/// Error type returned when parsing exported ROAs in the
+/// [`rpki`](crate::rpki) module
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RoaParseError {
+	entry: usize,
+	reason: RoaParseReason,
+}
+
+// only constructed by the parsers in `rpki`
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) enum RoaParseReason {
+	InvalidSyntax,
+	InvalidAsn,
+	InvalidMaxLength,
+	NetworkParseError(NetworkParseError),
+}
+
+#[cfg(feature = "alloc")]
+impl RoaParseError {
+	pub(crate) const fn new(entry: usize, reason: RoaParseReason) -> Self {
+		Self { entry, reason }
+	}
+
+	/// category of the error
+	pub const fn kind(&self) -> ErrorKind {
+		match &self.reason {
+			RoaParseReason::InvalidSyntax => ErrorKind::InvalidSyntax,
+			RoaParseReason::InvalidAsn => ErrorKind::InvalidSyntax,
+			RoaParseReason::InvalidMaxLength => ErrorKind::InvalidNetworkLength,
+			RoaParseReason::NetworkParseError(e) => e.kind(),
+		}
+	}
+
+	/// entry (starting at 1) the error occurred in: the line in CSV
+	/// input, the index in the `roas` array in JSON input (`0` if the
+	/// JSON document itself is invalid)
+	pub const fn entry(&self) -> usize {
+		self.entry
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for RoaParseError {
+	fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(w, "ROA entry {}: ", self.entry)?;
+		match &self.reason {
+			RoaParseReason::InvalidSyntax => write!(w, "invalid syntax"),
+			RoaParseReason::InvalidAsn => write!(w, "invalid AS number"),
+			RoaParseReason::InvalidMaxLength => write!(w, "invalid max length"),
+			RoaParseReason::NetworkParseError(e) => fmt::Display::fmt(e, w),
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl Error for RoaParseError {
+	fn description(&self) -> &str {
+		"ROA parse error"
+	}
+
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match &self.reason {
+			RoaParseReason::NetworkParseError(e) => Some(e),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		errors::{
+			ErrorKind,
+			InetTupleError,
+			NetworkParseError,
+		},
+		Family,
+		IpCidr,
+		IpInet,
+		Ipv4Cidr,
+		Ipv4Inet,
+		Ipv6Cidr,
+	};
+
+	fn message(e: &NetworkParseError) -> impl core::ops::Deref<Target = str> {
+		crate::display_buffer::format_fixed::<256>(format_args!("{}", e))
+	}
+
+	fn debug(e: &NetworkParseError) -> impl core::ops::Deref<Target = str> {
+		crate::display_buffer::format_fixed::<256>(format_args!("{:?}", e))
+	}
+
+	#[test]
+	fn invalid_host_part() {
+		let e = "10.0.0.1/8".parse::<Ipv4Cidr>().unwrap_err();
+		let NetworkParseError::InvalidHostPart(details) = &e else {
+			panic!("unexpected error: {:?}", e);
+		};
+		assert_eq!(details.inet(), "10.0.0.1/8".parse::<IpInet>().unwrap());
+		assert_eq!(details.network(), "10.0.0.0/8".parse::<IpCidr>().unwrap());
+		assert_eq!(
+			details.host_part(),
+			"0.0.0.1".parse::<core::net::IpAddr>().unwrap()
+		);
+		assert_eq!(
+			&*message(&e),
+			"host part of address was not zero: 10.0.0.1/8 has host bits 0.0.0.1; did you mean 10.0.0.0/8?"
+		);
+		assert_eq!(e.span("10.0.0.1/8"), Some(0..8));
+
+		let e = "2001:db8::1/32".parse::<IpCidr>().unwrap_err();
+		assert_eq!(
+			&*message(&e),
+			"host part of address was not zero: 2001:db8::1/32 has host bits ::1; did you mean 2001:db8::/32?"
+		);
+	}
+
+	#[test]
+	fn network_length() {
+		let input = "10.0.0.0/";
+		let e = input.parse::<Ipv4Cidr>().unwrap_err();
+		assert_eq!(e, NetworkParseError::EmptyNetworkLength);
+		assert_eq!(e.span(input), Some(9..9));
+
+		let input = "10.0.0.0/33";
+		let e = input.parse::<Ipv4Inet>().unwrap_err();
+		assert!(matches!(e, NetworkParseError::NetworkLengthTooLongError(_)));
+		assert_eq!(&input[e.span(input).unwrap()], "33");
+
+		let input = "10.0.0.0/x";
+		let e = input.parse::<IpCidr>().unwrap_err();
+		assert!(matches!(e, NetworkParseError::NetworkLengthParseError(_)));
+		assert_eq!(&input[e.span(input).unwrap()], "x");
+	}
+
+	#[test]
+	fn address() {
+		let input = "10.0.0.300/8";
+		let e = input.parse::<Ipv4Cidr>().unwrap_err();
+		assert!(matches!(e, NetworkParseError::AddrParseError(_)));
+		assert_eq!(&input[e.span(input).unwrap()], "10.0.0.300");
+
+		let input = "10.0.0.0/8/8";
+		let e = input.parse::<Ipv4Cidr>().unwrap_err();
+		assert_eq!(e, NetworkParseError::ExtraSeparator);
+		assert_eq!(e.span(input), Some(8..9));
+	}
+
+	#[test]
+	fn family_mismatch() {
+		let input = "2001:db8::/32";
+		let e = input.parse::<Ipv4Cidr>().unwrap_err();
+		assert_eq!(e, NetworkParseError::FamilyMismatch(Family::Ipv6));
+		assert_eq!(
+			&*message(&e),
+			"expected IPv4 address in network, found IPv6 address"
+		);
+		assert_eq!(&input[e.span(input).unwrap()], "2001:db8::");
+
+		let e = "192.0.2.1".parse::<Ipv6Cidr>().unwrap_err();
+		assert_eq!(e, NetworkParseError::FamilyMismatch(Family::Ipv4));
+		let e = "192.0.2.1/24".parse::<crate::Ipv6Inet>().unwrap_err();
+		assert_eq!(e, NetworkParseError::FamilyMismatch(Family::Ipv4));
+	}
+
+	#[test]
+	fn kind_and_debug() {
+		let e = "10.0.0.1/8".parse::<Ipv4Cidr>().unwrap_err();
+		assert_eq!(e.kind(), ErrorKind::InvalidHostPart);
+		assert_eq!(
+			&*debug(&e),
+			"InvalidHostPart(InvalidHostPartError { inet: V4(10.0.0.1/8) })"
+		);
+
+		let e = "10.0.0.0/33".parse::<Ipv4Cidr>().unwrap_err();
+		assert_eq!(e.kind(), ErrorKind::NetworkLengthTooLong);
+		assert_eq!(
+			&*debug(&e),
+			"NetworkLengthTooLongError(NetworkLengthTooLongError { network_length: 33, family: Ipv4 })"
+		);
+		assert_eq!(
+			&*message(&e),
+			"invalid length for network: Network length 33 is too long for Ipv4 (maximum: 32)"
+		);
+
+		assert_eq!(
+			"10.0.0.0/".parse::<Ipv4Cidr>().unwrap_err().kind(),
+			ErrorKind::InvalidNetworkLength
+		);
+		assert_eq!(
+			"10.0.0.256/8".parse::<Ipv4Cidr>().unwrap_err().kind(),
+			ErrorKind::InvalidAddress
+		);
+		assert_eq!(
+			NetworkParseError::AddressBitsOutOfRange.kind(),
+			ErrorKind::AddressOutOfRange
+		);
+		assert_eq!(
+			InetTupleError::NotInSharedNetwork.kind(),
+			ErrorKind::NotInSharedNetwork
+		);
+	}
+}
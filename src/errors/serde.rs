@@ -0,0 +1,172 @@
+#![cfg(feature = "serde")]
+
+// errors are serialized as struct with `kind` (`ErrorKind` variant name)
+// and `message` (the `Display` output), e.g. for API responses; they
+// can't be deserialized.
+
+use core::fmt;
+
+use serde::ser::SerializeStruct;
+
+use crate::errors::{
+	ConstParseError,
+	ErrorKind,
+	FromBytesError,
+	InetTupleError,
+	InvalidHostPartError,
+	NetworkLengthTooLongError,
+	NetworkParseError,
+	NlriBufferTooSmallError,
+	NlriDecodeError,
+};
+
+#[cfg(feature = "alloc")]
+use crate::errors::{
+	AllocationError,
+	PlanError,
+	PrefixFilterParseError,
+	RoaParseError,
+};
+
+impl serde::Serialize for ErrorKind {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		let (index, name) = match self {
+			Self::InvalidHostPart => (0, "InvalidHostPart"),
+			Self::InvalidAddress => (1, "InvalidAddress"),
+			Self::InvalidNetworkLength => (2, "InvalidNetworkLength"),
+			Self::NetworkLengthTooLong => (3, "NetworkLengthTooLong"),
+			Self::ExtraSeparator => (4, "ExtraSeparator"),
+			Self::FamilyMismatch => (5, "FamilyMismatch"),
+			Self::NotInSharedNetwork => (6, "NotInSharedNetwork"),
+			Self::UnexpectedEnd => (7, "UnexpectedEnd"),
+			Self::TrailingData => (8, "TrailingData"),
+			Self::BufferTooSmall => (9, "BufferTooSmall"),
+			Self::InvalidTag => (10, "InvalidTag"),
+			Self::NonZeroPadding => (11, "NonZeroPadding"),
+			Self::Exhausted => (12, "Exhausted"),
+			Self::NotInPool => (13, "NotInPool"),
+			Self::Overlapping => (14, "Overlapping"),
+			Self::NotAllocated => (15, "NotAllocated"),
+			Self::InvalidSyntax => (16, "InvalidSyntax"),
+			Self::AddressOutOfRange => (17, "AddressOutOfRange"),
+		};
+		serializer.serialize_unit_variant("ErrorKind", index, name)
+	}
+}
+
+// serialize `Display` output as string
+struct Message<'a, T>(&'a T);
+
+impl<T: fmt::Display> serde::Serialize for Message<'_, T> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serializer.collect_str(self.0)
+	}
+}
+
+macro_rules! impl_serialize_for {
+	($($t:ident)*) => {$(
+		impl serde::Serialize for $t {
+			fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+			where
+				S: serde::Serializer,
+			{
+				let mut s = serializer.serialize_struct(stringify!($t), 2)?;
+				s.serialize_field("kind", &self.kind())?;
+				s.serialize_field("message", &Message(self))?;
+				s.end()
+			}
+		}
+	)*};
+}
+
+impl_serialize_for! {
+	NetworkLengthTooLongError
+	InvalidHostPartError
+	NetworkParseError
+	ConstParseError
+	InetTupleError
+	NlriDecodeError
+	NlriBufferTooSmallError
+	FromBytesError
+}
+
+#[cfg(feature = "alloc")]
+impl_serialize_for! {
+	AllocationError
+	PlanError
+	PrefixFilterParseError
+	RoaParseError
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_test::{
+		assert_ser_tokens,
+		Token,
+	};
+
+	use crate::{
+		errors::{
+			ErrorKind,
+			InetTupleError,
+		},
+		Ipv4Cidr,
+	};
+
+	#[test]
+	fn kind() {
+		assert_ser_tokens(
+			&ErrorKind::InvalidHostPart,
+			&[Token::UnitVariant {
+				name: "ErrorKind",
+				variant: "InvalidHostPart",
+			}],
+		);
+	}
+
+	#[test]
+	fn errors() {
+		assert_ser_tokens(
+			&"10.0.0.1/8".parse::<Ipv4Cidr>().unwrap_err(),
+			&[
+				Token::Struct {
+					name: "NetworkParseError",
+					len: 2,
+				},
+				Token::Str("kind"),
+				Token::UnitVariant {
+					name: "ErrorKind",
+					variant: "InvalidHostPart",
+				},
+				Token::Str("message"),
+				Token::Str(
+					"host part of address was not zero: 10.0.0.1/8 has host bits 0.0.0.1; did you mean 10.0.0.0/8?",
+				),
+				Token::StructEnd,
+			],
+		);
+		assert_ser_tokens(
+			&InetTupleError::NotInSharedNetwork,
+			&[
+				Token::Struct {
+					name: "InetTupleError",
+					len: 2,
+				},
+				Token::Str("kind"),
+				Token::UnitVariant {
+					name: "ErrorKind",
+					variant: "NotInSharedNetwork",
+				},
+				Token::Str("message"),
+				Token::Str("addresses not in shared network"),
+				Token::StructEnd,
+			],
+		);
+	}
+}
@@ -4,8 +4,18 @@ use core::net::{
 	Ipv6Addr,
 };
 
+use crate::{
+	errors::NetworkParseError,
+	IpCidr,
+	IpInet,
+	Ipv4Cidr,
+	Ipv4Inet,
+	Ipv6Cidr,
+	Ipv6Inet,
+};
+
 /// Represents the type of an IP address
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub enum Family {
 	/// IPv4
 	Ipv4,
@@ -14,6 +24,14 @@ pub enum Family {
 }
 
 impl Family {
+	/// The family of the given address
+	pub const fn of(addr: &IpAddr) -> Self {
+		match addr {
+			IpAddr::V4(_) => Self::Ipv4,
+			IpAddr::V6(_) => Self::Ipv6,
+		}
+	}
+
 	/// The length of an address (as bitstring) in the given family
 	#[allow(clippy::len_without_is_empty)]
 	pub const fn len(&self) -> u8 {
@@ -38,4 +56,207 @@ impl Family {
 			Self::Ipv6 => IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)),
 		}
 	}
+
+	/// The maximum network length in the given family (same as
+	/// [`len`](Self::len))
+	pub const fn max_network_length(&self) -> u8 {
+		self.len()
+	}
+
+	/// The address with all bits set (`255.255.255.255` or
+	/// `ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff`) of the given family
+	///
+	/// For IPv4 this is the "limited broadcast" address.
+	pub const fn all_ones_address(&self) -> IpAddr {
+		match self {
+			Self::Ipv4 => IpAddr::V4(Ipv4Addr::BROADCAST),
+			Self::Ipv6 => IpAddr::V6(Ipv6Addr::from_bits(u128::MAX)),
+		}
+	}
+
+	/// The network containing all addresses (`0.0.0.0/0` or `::/0`) of
+	/// the given family
+	pub const fn whole_network(&self) -> IpCidr {
+		match self {
+			Self::Ipv4 => IpCidr::V4(Ipv4Cidr {
+				address: Ipv4Addr::UNSPECIFIED,
+				network_length: 0,
+			}),
+			Self::Ipv6 => IpCidr::V6(Ipv6Cidr {
+				address: Ipv6Addr::UNSPECIFIED,
+				network_length: 0,
+			}),
+		}
+	}
+
+	/// Parse an address of the given family
+	///
+	/// Fails with [`NetworkParseError::FamilyMismatch`] if the input is a
+	/// valid address of the other family.
+	pub fn parse_address(&self, s: &str) -> Result<IpAddr, NetworkParseError> {
+		match s.parse::<IpAddr>() {
+			Ok(addr) if Family::of(&addr) == *self => Ok(addr),
+			Ok(addr) => Err(NetworkParseError::FamilyMismatch(Family::of(&addr))),
+			Err(e) => Err(NetworkParseError::AddrParseError(e)),
+		}
+	}
+
+	/// Address of the given family from its numeric representation
+	///
+	/// Returns `None` if `bits` doesn't fit into an IPv4 address.
+	pub const fn address_from_bits(&self, bits: u128) -> Option<IpAddr> {
+		match self {
+			Self::Ipv4 => {
+				if bits > u32::MAX as u128 {
+					return None;
+				}
+				Some(IpAddr::V4(Ipv4Addr::from_bits(bits as u32)))
+			},
+			Self::Ipv6 => Some(IpAddr::V6(Ipv6Addr::from_bits(bits))),
+		}
+	}
+
+	/// Network of the given family from the numeric representation of the
+	/// first address and the network length
+	///
+	/// Fails like [`IpCidr::new`]; if `bits` doesn't fit into an IPv4
+	/// address [`NetworkParseError::AddressBitsOutOfRange`] is returned.
+	pub const fn cidr_from_bits(&self, bits: u128, len: u8) -> Result<IpCidr, NetworkParseError> {
+		match self.address_from_bits(bits) {
+			Some(addr) => IpCidr::new(addr, len),
+			None => Err(NetworkParseError::AddressBitsOutOfRange),
+		}
+	}
+
+	/// Host within a network of the given family from the numeric
+	/// representation of the address and the network length
+	///
+	/// Fails like [`IpInet::new`]; if `bits` doesn't fit into an IPv4
+	/// address [`NetworkParseError::AddressBitsOutOfRange`] is returned.
+	pub const fn inet_from_bits(&self, bits: u128, len: u8) -> Result<IpInet, NetworkParseError> {
+		match self.address_from_bits(bits) {
+			Some(IpAddr::V4(addr)) => match Ipv4Inet::new(addr, len) {
+				Ok(inet) => Ok(IpInet::V4(inet)),
+				Err(e) => Err(NetworkParseError::NetworkLengthTooLongError(e)),
+			},
+			Some(IpAddr::V6(addr)) => match Ipv6Inet::new(addr, len) {
+				Ok(inet) => Ok(IpInet::V6(inet)),
+				Err(e) => Err(NetworkParseError::NetworkLengthTooLongError(e)),
+			},
+			None => Err(NetworkParseError::AddressBitsOutOfRange),
+		}
+	}
+}
+
+impl From<IpAddr> for Family {
+	fn from(addr: IpAddr) -> Self {
+		Self::of(&addr)
+	}
+}
+
+impl From<Ipv4Addr> for Family {
+	fn from(_: Ipv4Addr) -> Self {
+		Self::Ipv4
+	}
+}
+
+impl From<Ipv6Addr> for Family {
+	fn from(_: Ipv6Addr) -> Self {
+		Self::Ipv6
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::net::IpAddr;
+
+	use crate::{
+		errors::NetworkParseError,
+		Family,
+	};
+
+	#[test]
+	fn of() {
+		let addr: IpAddr = "2001:db8::1".parse().unwrap();
+		assert_eq!(Family::of(&addr), Family::Ipv6);
+		assert_eq!(Family::from(addr), Family::Ipv6);
+		assert_eq!(
+			Family::from("192.0.2.1".parse::<IpAddr>().unwrap()),
+			Family::Ipv4
+		);
+	}
+
+	#[test]
+	fn addresses() {
+		assert_eq!(
+			Family::Ipv4.all_ones_address(),
+			"255.255.255.255".parse::<IpAddr>().unwrap()
+		);
+		assert_eq!(
+			Family::Ipv6.all_ones_address(),
+			"ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff"
+				.parse::<IpAddr>()
+				.unwrap()
+		);
+		assert_eq!(Family::Ipv6.max_network_length(), 128);
+		assert_eq!(Family::Ipv4.whole_network(), "0.0.0.0/0".parse().unwrap());
+		assert_eq!(Family::Ipv6.whole_network(), "::/0".parse().unwrap());
+	}
+
+	#[test]
+	fn parse_address() {
+		assert_eq!(
+			Family::Ipv4.parse_address("192.0.2.1"),
+			Ok("192.0.2.1".parse().unwrap())
+		);
+		assert_eq!(
+			Family::Ipv4.parse_address("::1"),
+			Err(NetworkParseError::FamilyMismatch(Family::Ipv6))
+		);
+		assert_eq!(
+			Family::Ipv6.parse_address("192.0.2.1"),
+			Err(NetworkParseError::FamilyMismatch(Family::Ipv4))
+		);
+		assert!(matches!(
+			Family::Ipv6.parse_address("foo"),
+			Err(NetworkParseError::AddrParseError(_))
+		));
+	}
+
+	#[test]
+	fn from_bits() {
+		assert_eq!(
+			Family::Ipv4.address_from_bits(0xc000_0201),
+			Some("192.0.2.1".parse().unwrap())
+		);
+		assert_eq!(Family::Ipv4.address_from_bits(1 << 32), None);
+		assert_eq!(
+			Family::Ipv6.address_from_bits(1),
+			Some("::1".parse().unwrap())
+		);
+		assert_eq!(
+			Family::Ipv4.cidr_from_bits(0xc000_0200, 24),
+			Ok("192.0.2.0/24".parse().unwrap())
+		);
+		assert!(matches!(
+			Family::Ipv4.cidr_from_bits(0xc000_0201, 24),
+			Err(NetworkParseError::InvalidHostPart(_))
+		));
+		assert_eq!(
+			Family::Ipv6.inet_from_bits(0x2001_0db8 << 96 | 1, 32),
+			Ok("2001:db8::1/32".parse().unwrap())
+		);
+		assert!(matches!(
+			Family::Ipv4.inet_from_bits(0, 33),
+			Err(NetworkParseError::NetworkLengthTooLongError(_))
+		));
+		assert_eq!(
+			Family::Ipv4.inet_from_bits(1 << 32, 0),
+			Err(NetworkParseError::AddressBitsOutOfRange)
+		);
+		assert_eq!(
+			Family::Ipv4.cidr_from_bits(1 << 32, 32),
+			Err(NetworkParseError::AddressBitsOutOfRange)
+		);
+	}
 }
@@ -0,0 +1,262 @@
+use core::{
+	fmt,
+	net::{
+		IpAddr,
+		Ipv4Addr,
+		Ipv6Addr,
+	},
+	str::FromStr,
+};
+
+use crate::{
+	errors::*,
+	AnyIpCidr,
+	Family,
+	IpInet,
+	Ipv4Inet,
+	Ipv6Inet,
+};
+
+/// Represents either an IPv4 or an IPv6 host within a network or "any".
+///
+/// The [`Inet`] counterpart of [`AnyIpCidr`]; "any" has no host address.
+///
+/// The [`Inet`] trait cannot be implemented for this type.
+///
+/// [`Inet`]: crate::Inet
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum AnyIpInet {
+	/// "any" network containing all IPv4 and IPv6 addresses
+	Any,
+	/// IPv4 host within network
+	V4(Ipv4Inet),
+	/// IPv6 host within network
+	V6(Ipv6Inet),
+}
+
+impl AnyIpInet {
+	/// Whether representing any address
+	pub const fn is_any(&self) -> bool {
+		match self {
+			Self::Any => true,
+			_ => false,
+		}
+	}
+
+	/// Whether representing an IPv4 host
+	pub const fn is_ipv4(&self) -> bool {
+		match self {
+			Self::V4(_) => true,
+			_ => false,
+		}
+	}
+
+	/// Whether representing an IPv6 host
+	pub const fn is_ipv6(&self) -> bool {
+		match self {
+			Self::V6(_) => true,
+			_ => false,
+		}
+	}
+}
+
+// "Inet" functions
+impl AnyIpInet {
+	/// Create new host within a network from address and prefix length.
+	/// If the network length exceeds the address length an error is
+	/// returned.
+	pub const fn new(addr: IpAddr, len: u8) -> Result<Self, NetworkLengthTooLongError> {
+		match IpInet::new(addr, len) {
+			Ok(IpInet::V4(inet)) => Ok(Self::V4(inet)),
+			Ok(IpInet::V6(inet)) => Ok(Self::V6(inet)),
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Create a network containing a single address as host and the
+	/// network (network length = address length).
+	pub const fn new_host(addr: IpAddr) -> Self {
+		match addr {
+			IpAddr::V4(a) => Self::V4(Ipv4Inet::new_host(a)),
+			IpAddr::V6(a) => Self::V6(Ipv6Inet::new_host(a)),
+		}
+	}
+
+	/// network (i.e. drops the host information)
+	pub const fn network(&self) -> AnyIpCidr {
+		match self {
+			Self::Any => AnyIpCidr::Any,
+			Self::V4(c) => AnyIpCidr::V4(c.network()),
+			Self::V6(c) => AnyIpCidr::V6(c.network()),
+		}
+	}
+
+	/// the host
+	///
+	/// returns [`None`] for [`Any`]
+	///
+	/// [`Any`]: Self::Any
+	pub const fn address(&self) -> Option<IpAddr> {
+		match self {
+			Self::Any => None,
+			Self::V4(c) => Some(IpAddr::V4(c.address())),
+			Self::V6(c) => Some(IpAddr::V6(c.address())),
+		}
+	}
+
+	/// first address in the network as plain address
+	///
+	/// returns [`None`] for [`Any`]
+	///
+	/// [`Any`]: Self::Any
+	pub const fn first_address(&self) -> Option<IpAddr> {
+		self.network().first_address()
+	}
+
+	/// first address in the network
+	///
+	/// returns [`None`] for [`Any`]
+	///
+	/// [`Any`]: Self::Any
+	pub const fn first(&self) -> Option<IpInet> {
+		self.network().first()
+	}
+
+	/// last address in the network as plain address
+	///
+	/// returns [`None`] for [`Any`]
+	///
+	/// [`Any`]: Self::Any
+	pub const fn last_address(&self) -> Option<IpAddr> {
+		self.network().last_address()
+	}
+
+	/// last address in the network
+	///
+	/// returns [`None`] for [`Any`]
+	///
+	/// [`Any`]: Self::Any
+	pub const fn last(&self) -> Option<IpInet> {
+		self.network().last()
+	}
+
+	/// length in bits of the shared prefix of the contained addresses
+	///
+	/// returns [`None`] for [`Any`]
+	///
+	/// [`Any`]: Self::Any
+	pub const fn network_length(&self) -> Option<u8> {
+		self.network().network_length()
+	}
+
+	/// IP family of the contained address ([`Ipv4`] or [`Ipv6`]).
+	///
+	/// returns [`None`] for [`Any`]
+	///
+	/// [`Any`]: Self::Any
+	/// [`Ipv4`]: Family::Ipv4
+	/// [`Ipv6`]: Family::Ipv6
+	pub const fn family(&self) -> Option<Family> {
+		self.network().family()
+	}
+
+	/// whether network represents a single host address
+	pub const fn is_host_address(&self) -> bool {
+		self.network().is_host_address()
+	}
+
+	/// network mask: an pseudo address which has the first `network
+	/// length` bits set to 1 and the remaining to 0.
+	///
+	/// returns [`None`] for [`Any`]
+	///
+	/// [`Any`]: Self::Any
+	pub const fn mask(&self) -> Option<IpAddr> {
+		self.network().mask()
+	}
+
+	/// check whether an address is contained in the network
+	pub const fn contains(&self, addr: &IpAddr) -> bool {
+		self.network().contains(addr)
+	}
+}
+
+impl fmt::Display for AnyIpInet {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Any => write!(f, "any"),
+			Self::V4(c) => fmt::Display::fmt(c, f),
+			Self::V6(c) => fmt::Display::fmt(c, f),
+		}
+	}
+}
+
+impl From<AnyIpInet> for Option<IpInet> {
+	fn from(value: AnyIpInet) -> Option<IpInet> {
+		match value {
+			AnyIpInet::Any => None,
+			AnyIpInet::V4(c) => Some(IpInet::V4(c)),
+			AnyIpInet::V6(c) => Some(IpInet::V6(c)),
+		}
+	}
+}
+
+impl From<Option<IpInet>> for AnyIpInet {
+	fn from(a: Option<IpInet>) -> Self {
+		match a {
+			None => Self::Any,
+			Some(IpInet::V4(c)) => Self::V4(c),
+			Some(IpInet::V6(c)) => Self::V6(c),
+		}
+	}
+}
+
+impl FromStr for AnyIpInet {
+	type Err = NetworkParseError;
+
+	fn from_str(s: &str) -> Result<Self, NetworkParseError> {
+		if s == "any" {
+			return Ok(Self::Any);
+		}
+		Ok(s.parse::<IpInet>()?.into())
+	}
+}
+
+impl From<IpInet> for AnyIpInet {
+	fn from(c: IpInet) -> Self {
+		match c {
+			IpInet::V4(c) => Self::V4(c),
+			IpInet::V6(c) => Self::V6(c),
+		}
+	}
+}
+
+impl From<Ipv4Inet> for AnyIpInet {
+	fn from(c: Ipv4Inet) -> Self {
+		Self::V4(c)
+	}
+}
+
+impl From<Ipv4Addr> for AnyIpInet {
+	fn from(address: Ipv4Addr) -> Self {
+		Self::V4(address.into())
+	}
+}
+
+impl From<Ipv6Inet> for AnyIpInet {
+	fn from(c: Ipv6Inet) -> Self {
+		Self::V6(c)
+	}
+}
+
+impl From<Ipv6Addr> for AnyIpInet {
+	fn from(address: Ipv6Addr) -> Self {
+		Self::V6(address.into())
+	}
+}
+
+impl From<IpAddr> for AnyIpInet {
+	fn from(address: IpAddr) -> Self {
+		Self::new_host(address)
+	}
+}
@@ -63,12 +63,34 @@ impl IpInet {
 		}
 	}
 
+	/// Create new host within a network of the given family from the numeric
+	/// representation of the address and prefix length
+	///
+	/// See [`Family::inet_from_bits`]; unlike [`new`](Self::new) this
+	/// returns a [`NetworkParseError`], as it also fails with
+	/// [`NetworkParseError::AddressBitsOutOfRange`] if `bits` doesn't fit
+	/// into an IPv4 address.
+	pub const fn from_bits(family: Family, bits: u128, len: u8) -> Result<Self, NetworkParseError> {
+		family.inet_from_bits(bits, len)
+	}
+
+	/// Numeric representation of the address and the network length
+	pub const fn to_bits(&self) -> (u128, u8) {
+		match self {
+			Self::V4(c) => {
+				let (bits, len) = c.to_bits();
+				(bits as u128, len)
+			},
+			Self::V6(c) => c.to_bits(),
+		}
+	}
+
 	/// increments host part (without changing the network part);
 	/// returns true on wrap around
 	pub fn increment(&mut self) -> bool {
 		match self {
-			Self::V4(mut c) => c.increment(),
-			Self::V6(mut c) => c.increment(),
+			Self::V4(c) => c.increment(),
+			Self::V6(c) => c.increment(),
 		}
 	}
 
@@ -90,8 +112,8 @@ impl IpInet {
 	/// returns true on wrap around
 	pub fn decrement(&mut self) -> bool {
 		match self {
-			Self::V4(mut c) => c.decrement(),
-			Self::V6(mut c) => c.decrement(),
+			Self::V4(c) => c.decrement(),
+			Self::V6(c) => c.decrement(),
 		}
 	}
 
@@ -141,6 +163,101 @@ impl IpInet {
 		}
 	}
 
+	/// Find the nth host after the current one in the current network
+	///
+	/// Returns `None` if the result would leave the network.
+	pub const fn checked_add(self, step: u128) -> Option<Self> {
+		match self {
+			Self::V4(c) => match c.checked_add(step) {
+				Some(c) => Some(Self::V4(c)),
+				None => None,
+			},
+			Self::V6(c) => match c.checked_add(step) {
+				Some(c) => Some(Self::V6(c)),
+				None => None,
+			},
+		}
+	}
+
+	/// Find the nth host before the current one in the current network
+	///
+	/// Returns `None` if the result would leave the network.
+	pub const fn checked_sub(self, step: u128) -> Option<Self> {
+		match self {
+			Self::V4(c) => match c.checked_sub(step) {
+				Some(c) => Some(Self::V4(c)),
+				None => None,
+			},
+			Self::V6(c) => match c.checked_sub(step) {
+				Some(c) => Some(Self::V6(c)),
+				None => None,
+			},
+		}
+	}
+
+	/// Find the nth host after the current one in the current network
+	///
+	/// Returns the last address in the network if the result would leave
+	/// the network.
+	pub const fn saturating_add(self, step: u128) -> Self {
+		match self {
+			Self::V4(c) => Self::V4(c.saturating_add(step)),
+			Self::V6(c) => Self::V6(c.saturating_add(step)),
+		}
+	}
+
+	/// Find the nth host before the current one in the current network
+	///
+	/// Returns the first address in the network if the result would leave
+	/// the network.
+	pub const fn saturating_sub(self, step: u128) -> Self {
+		match self {
+			Self::V4(c) => Self::V4(c.saturating_sub(step)),
+			Self::V6(c) => Self::V6(c.saturating_sub(step)),
+		}
+	}
+
+	/// Find the nth host after the current one in the current network
+	///
+	/// Wraps around within the network (only the host part changes).
+	pub const fn wrapping_add(self, step: u128) -> Self {
+		match self {
+			Self::V4(c) => Self::V4(c.wrapping_add(step)),
+			Self::V6(c) => Self::V6(c.wrapping_add(step)),
+		}
+	}
+
+	/// Find the nth host before the current one in the current network
+	///
+	/// Wraps around within the network (only the host part changes).
+	pub const fn wrapping_sub(self, step: u128) -> Self {
+		match self {
+			Self::V4(c) => Self::V4(c.wrapping_sub(step)),
+			Self::V6(c) => Self::V6(c.wrapping_sub(step)),
+		}
+	}
+
+	/// Index of the host in the network (i.e. the host part of the address
+	/// as number)
+	pub const fn offset_in_network(&self) -> u128 {
+		match self {
+			Self::V4(c) => c.offset_in_network(),
+			Self::V6(c) => c.offset_in_network(),
+		}
+	}
+
+	/// Absolute difference between the addresses (the network lengths are
+	/// ignored)
+	///
+	/// Returns `None` if the addresses are from different families.
+	pub const fn distance(&self, other: &Self) -> Option<u128> {
+		match (self, other) {
+			(Self::V4(a), Self::V4(b)) => Some(a.distance(b)),
+			(Self::V6(a), Self::V6(b)) => Some(a.distance(b)),
+			_ => None,
+		}
+	}
+
 	/// network (i.e. drops the host information)
 	pub const fn network(&self) -> IpCidr {
 		match self {
@@ -277,6 +394,34 @@ impl Inet for IpInet {
 		self.overflowing_sub(step)
 	}
 
+	fn checked_add(self, step: u128) -> Option<Self> {
+		self.checked_add(step)
+	}
+
+	fn checked_sub(self, step: u128) -> Option<Self> {
+		self.checked_sub(step)
+	}
+
+	fn saturating_add(self, step: u128) -> Self {
+		self.saturating_add(step)
+	}
+
+	fn saturating_sub(self, step: u128) -> Self {
+		self.saturating_sub(step)
+	}
+
+	fn wrapping_add(self, step: u128) -> Self {
+		self.wrapping_add(step)
+	}
+
+	fn wrapping_sub(self, step: u128) -> Self {
+		self.wrapping_sub(step)
+	}
+
+	fn offset_in_network(&self) -> u128 {
+		self.offset_in_network()
+	}
+
 	fn network(&self) -> IpCidr {
 		self.network()
 	}
@@ -388,3 +533,15 @@ impl core::ops::Sub<u128> for IpInet {
 		result
 	}
 }
+
+impl core::ops::AddAssign<u128> for IpInet {
+	fn add_assign(&mut self, step: u128) {
+		*self = *self + step;
+	}
+}
+
+impl core::ops::SubAssign<u128> for IpInet {
+	fn sub_assign(&mut self, step: u128) {
+		*self = *self - step;
+	}
+}
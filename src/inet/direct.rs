@@ -25,7 +25,7 @@ use crate::{
 };
 
 macro_rules! impl_inet_for {
-	($n:ident : cidr $cidr:ident : addr $addr:ident : pair $pair:ident : family $family:expr) => {
+	($n:ident : cidr $cidr:ident : addr $addr:ident : pair $pair:ident : native $native:ident : family $family:expr) => {
 		#[cfg(feature = "bitstring")]
 		impl bitstring::BitString for $n {
 			fn get(&self, ndx: usize) -> bool {
@@ -99,6 +99,24 @@ macro_rules! impl_inet_for {
 				}
 			}
 
+			/// Create new host within a network from the numeric
+			/// representation of the address and prefix length; fails like
+			/// [`new`](Self::new).
+			pub const fn from_bits(
+				bits: $native,
+				len: u8,
+			) -> Result<Self, NetworkLengthTooLongError> {
+				Self::new(<$addr as PrivUnspecAddress>::_Tools::from_native(bits), len)
+			}
+
+			/// Numeric representation of the address and the network length
+			pub const fn to_bits(&self) -> ($native, u8) {
+				(
+					<$addr as PrivUnspecAddress>::_Tools::to_native(self.address),
+					self.network_length,
+				)
+			}
+
 			/// increments host part (without changing the network part);
 			/// returns true on wrap around
 			pub fn increment(&mut self) -> bool {
@@ -189,6 +207,84 @@ macro_rules! impl_inet_for {
 				)
 			}
 
+			/// Find the nth host after the current one in the current network
+			///
+			/// Returns `None` if the result would leave the network.
+			pub const fn checked_add(self, step: u128) -> Option<Self> {
+				match self.overflowing_add(step) {
+					(result, false) => Some(result),
+					(_, true) => None,
+				}
+			}
+
+			/// Find the nth host before the current one in the current network
+			///
+			/// Returns `None` if the result would leave the network.
+			pub const fn checked_sub(self, step: u128) -> Option<Self> {
+				match self.overflowing_sub(step) {
+					(result, false) => Some(result),
+					(_, true) => None,
+				}
+			}
+
+			/// Find the nth host after the current one in the current network
+			///
+			/// Returns the last address in the network if the result would
+			/// leave the network.
+			pub const fn saturating_add(self, step: u128) -> Self {
+				match self.overflowing_add(step) {
+					(result, false) => result,
+					(_, true) => self.last(),
+				}
+			}
+
+			/// Find the nth host before the current one in the current network
+			///
+			/// Returns the first address in the network if the result would
+			/// leave the network.
+			pub const fn saturating_sub(self, step: u128) -> Self {
+				match self.overflowing_sub(step) {
+					(result, false) => result,
+					(_, true) => self.first(),
+				}
+			}
+
+			/// Find the nth host after the current one in the current network
+			///
+			/// Wraps around within the network (only the host part changes).
+			pub const fn wrapping_add(self, step: u128) -> Self {
+				self.overflowing_add(step).0
+			}
+
+			/// Find the nth host before the current one in the current network
+			///
+			/// Wraps around within the network (only the host part changes).
+			pub const fn wrapping_sub(self, step: u128) -> Self {
+				self.overflowing_sub(step).0
+			}
+
+			/// Index of the host in the network (i.e. the host part of the
+			/// address as number)
+			pub const fn offset_in_network(&self) -> u128 {
+				<$addr as PrivUnspecAddress>::_Tools::_host_part(self.address, self.network_length)
+			}
+
+			// host part as address (network part set to zero)
+			pub(crate) const fn _host_part_address(&self) -> $addr {
+				<$addr as PrivUnspecAddress>::_Tools::from_native(
+					<$addr as PrivUnspecAddress>::_Tools::to_native(self.address)
+						& <$addr as PrivUnspecAddress>::_Tools::native_host_mask(
+							self.network_length,
+						),
+				)
+			}
+
+			/// Absolute difference between the addresses (the network lengths
+			/// are ignored)
+			pub const fn distance(&self, other: &Self) -> u128 {
+				<$addr as PrivUnspecAddress>::_Tools::_distance(self.address, other.address)
+			}
+
 			/// network (i.e. drops the host information)
 			pub const fn network(&self) -> $cidr {
 				$cidr {
@@ -305,6 +401,34 @@ macro_rules! impl_inet_for {
 				self.overflowing_sub(step)
 			}
 
+			fn checked_add(self, step: u128) -> Option<Self> {
+				self.checked_add(step)
+			}
+
+			fn checked_sub(self, step: u128) -> Option<Self> {
+				self.checked_sub(step)
+			}
+
+			fn saturating_add(self, step: u128) -> Self {
+				self.saturating_add(step)
+			}
+
+			fn saturating_sub(self, step: u128) -> Self {
+				self.saturating_sub(step)
+			}
+
+			fn wrapping_add(self, step: u128) -> Self {
+				self.wrapping_add(step)
+			}
+
+			fn wrapping_sub(self, step: u128) -> Self {
+				self.wrapping_sub(step)
+			}
+
+			fn offset_in_network(&self) -> u128 {
+				self.offset_in_network()
+			}
+
 			fn network(&self) -> $cidr {
 				self.network()
 			}
@@ -372,6 +496,7 @@ macro_rules! impl_inet_for {
 
 			fn from_str(s: &str) -> Result<Self, NetworkParseError> {
 				crate::parsers::parse_inet(s, FromStr::from_str)
+					.map_err(|e| e.check_family(s, $family))
 			}
 		}
 
@@ -381,6 +506,20 @@ macro_rules! impl_inet_for {
 			}
 		}
 
+		impl From<$n> for ($native, u8) {
+			fn from(inet: $n) -> Self {
+				inet.to_bits()
+			}
+		}
+
+		impl TryFrom<($native, u8)> for $n {
+			type Error = NetworkLengthTooLongError;
+
+			fn try_from((bits, len): ($native, u8)) -> Result<Self, NetworkLengthTooLongError> {
+				Self::from_bits(bits, len)
+			}
+		}
+
 		impl core::ops::Add<u128> for $n {
 			type Output = $n;
 
@@ -400,11 +539,23 @@ macro_rules! impl_inet_for {
 				result
 			}
 		}
+
+		impl core::ops::AddAssign<u128> for $n {
+			fn add_assign(&mut self, step: u128) {
+				*self = *self + step;
+			}
+		}
+
+		impl core::ops::SubAssign<u128> for $n {
+			fn sub_assign(&mut self, step: u128) {
+				*self = *self - step;
+			}
+		}
 	};
 }
 
-impl_inet_for! {Ipv4Inet : cidr Ipv4Cidr : addr Ipv4Addr : pair Ipv4InetPair : family Family::Ipv4}
-impl_inet_for! {Ipv6Inet : cidr Ipv6Cidr : addr Ipv6Addr : pair Ipv6InetPair : family Family::Ipv6}
+impl_inet_for! {Ipv4Inet : cidr Ipv4Cidr : addr Ipv4Addr : pair Ipv4InetPair : native u32 : family Family::Ipv4}
+impl_inet_for! {Ipv6Inet : cidr Ipv6Cidr : addr Ipv6Addr : pair Ipv6InetPair : native u128 : family Family::Ipv6}
 
 impl Ipv4Inet {
 	/// Find the nth host after the current one in the current network (32-bit IPv4 variant)
@@ -463,3 +614,15 @@ impl core::ops::Sub<u32> for Ipv4Inet {
 		result
 	}
 }
+
+impl core::ops::AddAssign<u32> for Ipv4Inet {
+	fn add_assign(&mut self, step: u32) {
+		*self = *self + step;
+	}
+}
+
+impl core::ops::SubAssign<u32> for Ipv4Inet {
+	fn sub_assign(&mut self, step: u32) {
+		*self = *self - step;
+	}
+}
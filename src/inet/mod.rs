@@ -1,3 +1,6 @@
+pub use self::any::AnyIpInet;
+
+mod any;
 mod combined;
 mod direct;
 mod serde;
@@ -2,6 +2,7 @@
 
 use crate::{
 	inet::{
+		AnyIpInet,
 		IpInet,
 		Ipv4Inet,
 		Ipv6Inet,
@@ -111,5 +112,43 @@ impl<'de> serde::Deserialize<'de> for IpInet {
 	}
 }
 
+static NAME_ANY_IP_INET: &str = "AnyIpInet";
+
+impl serde::Serialize for AnyIpInet {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		if serializer.is_human_readable() {
+			serializer.serialize_str(format_cidr_fixed!("{}", self))
+		} else {
+			let data = match self {
+				Self::Any => None,
+				Self::V4(i) => Some((IpAddr::V4(i.address), i.network_length)),
+				Self::V6(i) => Some((IpAddr::V6(i.address), i.network_length)),
+			};
+			serde_common::serialize_any(serializer, NAME_ANY_IP_INET, data)
+		}
+	}
+}
+
+impl<'de> serde::Deserialize<'de> for AnyIpInet {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		if deserializer.is_human_readable() {
+			serde_common::deserialize_parse(deserializer)
+		} else {
+			match serde_common::deserialize_any(deserializer, NAME_ANY_IP_INET)? {
+				None => Ok(Self::Any),
+				Some((addr, network_length)) => {
+					Self::new(addr, network_length).map_err(serde::de::Error::custom)
+				},
+			}
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests;
@@ -1,4 +1,5 @@
 use crate::{
+	AnyIpInet,
 	IpInet,
 	Ipv4Inet,
 	Ipv6Inet,
@@ -548,3 +549,48 @@ fn test_inet_v6_host() {
 		],
 	);
 }
+
+#[test]
+fn test_any_inet_any() {
+	let c: AnyIpInet = "any".parse().unwrap();
+
+	assert_tokens(&c.readable(), &[Token::Str("any")]);
+
+	assert_bincode(&c.compact(), &[0xff]);
+
+	assert_tokens(
+		&c.compact(),
+		&[
+			Token::NewtypeStruct { name: "AnyIpInet" },
+			Token::Tuple { len: 2 },
+			Token::U8(0xff),
+			Token::Unit,
+			Token::TupleEnd,
+		],
+	);
+}
+
+#[test]
+fn test_any_inet_v4() {
+	let c: AnyIpInet = "192.0.2.1/24".parse().unwrap();
+
+	assert_tokens(&c.readable(), &[Token::Str("192.0.2.1/24")]);
+
+	assert_bincode(&c.compact(), &[24, 192, 0, 2, 1]);
+
+	assert_tokens(
+		&c.compact(),
+		&[
+			Token::NewtypeStruct { name: "AnyIpInet" },
+			Token::Tuple { len: 2 },
+			Token::U8(24),
+			Token::Tuple { len: 4 },
+			Token::U8(192),
+			Token::U8(0),
+			Token::U8(2),
+			Token::U8(1),
+			Token::TupleEnd,
+			Token::TupleEnd,
+		],
+	);
+}
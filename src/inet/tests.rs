@@ -10,6 +10,8 @@ use core::{
 
 use crate::{
 	errors::NetworkParseError,
+	AnyIpCidr,
+	AnyIpInet,
 	Inet,
 	IpInet,
 	Ipv4Inet,
@@ -373,17 +375,13 @@ fn test_order(o: Ordering, a: &'static str, b: &'static str) {
 }
 
 #[test]
-#[should_panic(
-	expected = "invalid length for network: Network length 33 is too long for Ipv4 (maximum: 32)"
-)]
+#[should_panic(expected = "NetworkLengthTooLongError { network_length: 33, family: Ipv4 }")]
 fn parse_v4_33bit() {
 	"192.0.2.48/33".parse::<Ipv4Inet>().unwrap();
 }
 
 #[test]
-#[should_panic(
-	expected = "invalid length for network: Network length 33 is too long for Ipv4 (maximum: 32)"
-)]
+#[should_panic(expected = "NetworkLengthTooLongError { network_length: 33, family: Ipv4 }")]
 fn parse_v4_33bit_2() {
 	"192.0.2.48/33".parse::<IpInet>().unwrap();
 }
@@ -649,17 +647,13 @@ fn contains_not_v4_24bit_2() {
 }
 
 #[test]
-#[should_panic(
-	expected = "invalid length for network: Network length 129 is too long for Ipv6 (maximum: 128)"
-)]
+#[should_panic(expected = "NetworkLengthTooLongError { network_length: 129, family: Ipv6 }")]
 fn parse_v6_129bit() {
 	"2001:DB8::/129".parse::<Ipv6Inet>().unwrap();
 }
 
 #[test]
-#[should_panic(
-	expected = "invalid length for network: Network length 129 is too long for Ipv6 (maximum: 128)"
-)]
+#[should_panic(expected = "NetworkLengthTooLongError { network_length: 129, family: Ipv6 }")]
 fn parse_v6_33bit_2() {
 	"2001:DB8::/129".parse::<IpInet>().unwrap();
 }
@@ -992,18 +986,38 @@ where
 	I: Inet
 		+ FromStr<Err = NetworkParseError>
 		+ core::ops::Add<u128, Output = I>
-		+ core::ops::Sub<u128, Output = I>,
+		+ core::ops::Sub<u128, Output = I>
+		+ core::ops::AddAssign<u128>
+		+ core::ops::SubAssign<u128>,
 {
 	let a = a.parse::<I>().unwrap();
 	let b = b.parse::<I>().unwrap();
 
 	assert_eq!(a.overflowing_add(step), (b, overflow));
 	assert_eq!(b.overflowing_sub(step), (a, overflow));
+	assert_eq!(a.wrapping_add(step), b);
+	assert_eq!(b.wrapping_sub(step), a);
 
 	if !overflow {
+		assert_eq!(a.checked_add(step), Some(b));
+		assert_eq!(b.checked_sub(step), Some(a));
+		assert_eq!(a.saturating_add(step), b);
+		assert_eq!(b.saturating_sub(step), a);
+
 		// overflow would trigger debug asserts here
 		assert_eq!(a + step, b);
 		assert_eq!(b - step, a);
+
+		let mut c = a;
+		c += step;
+		assert_eq!(c, b);
+		c -= step;
+		assert_eq!(c, a);
+	} else {
+		assert_eq!(a.checked_add(step), None);
+		assert_eq!(b.checked_sub(step), None);
+		assert_eq!(a.saturating_add(step), a.last());
+		assert_eq!(b.saturating_sub(step), b.first());
 	}
 }
 
@@ -1050,3 +1064,108 @@ fn test_nth_v6() {
 		true,
 	);
 }
+
+#[test]
+fn test_offset_in_network() {
+	let inet = "192.0.2.23/24".parse::<Ipv4Inet>().unwrap();
+	assert_eq!(inet.offset_in_network(), 23);
+	assert_eq!(inet.first().offset_in_network(), 0);
+	assert_eq!(inet.last().offset_in_network(), 255);
+	assert_eq!(IpInet::V4(inet).offset_in_network(), 23);
+
+	let inet = "2001:DB8:1234:5678::23/64".parse::<Ipv6Inet>().unwrap();
+	assert_eq!(inet.offset_in_network(), 0x23);
+	assert_eq!(inet.last().offset_in_network(), u64::MAX as u128);
+
+	let inet = "::/0".parse::<Ipv6Inet>().unwrap();
+	assert_eq!(inet.last().offset_in_network(), u128::MAX);
+}
+
+#[test]
+fn test_distance() {
+	let a = "192.0.2.23/24".parse::<Ipv4Inet>().unwrap();
+	let b = "192.0.3.1/16".parse::<Ipv4Inet>().unwrap();
+	assert_eq!(a.distance(&b), 234);
+	assert_eq!(b.distance(&a), 234);
+	assert_eq!(a.distance(&a), 0);
+
+	let c = "::/0".parse::<Ipv6Inet>().unwrap();
+	assert_eq!(c.distance(&c.last()), u128::MAX);
+
+	assert_eq!(IpInet::V4(a).distance(&IpInet::V4(b)), Some(234));
+	assert_eq!(IpInet::V4(a).distance(&IpInet::V6(c)), None);
+}
+
+#[test]
+fn test_ip_increment_decrement() {
+	let mut inet = "192.0.2.255/24".parse::<IpInet>().unwrap();
+	assert!(inet.increment());
+	assert_eq!(inet, "192.0.2.0/24".parse().unwrap());
+	assert!(!inet.increment());
+	assert_eq!(inet, "192.0.2.1/24".parse().unwrap());
+	assert!(!inet.decrement());
+	assert!(inet.decrement());
+	assert_eq!(inet, "192.0.2.255/24".parse().unwrap());
+
+	let mut inet = "2001:DB8::1/64".parse::<IpInet>().unwrap();
+	assert!(!inet.increment());
+	assert_eq!(inet, "2001:DB8::2/64".parse().unwrap());
+}
+
+#[test]
+fn test_any_inet() {
+	let any = "any".parse::<AnyIpInet>().unwrap();
+	assert_eq!(any, AnyIpInet::Any);
+	assert!(any.is_any() && !any.is_ipv4() && !any.is_ipv6());
+	assert_eq!(any.address(), None);
+	assert_eq!(any.network(), AnyIpCidr::Any);
+	assert_eq!(any.network_length(), None);
+	assert!(any.contains(&"2001:db8::1".parse().unwrap()));
+	assert!(!any.is_host_address());
+
+	let inet = "192.0.2.1/24".parse::<AnyIpInet>().unwrap();
+	assert!(inet.is_ipv4());
+	assert_eq!(inet.address(), Some("192.0.2.1".parse().unwrap()));
+	assert_eq!(inet.network(), "192.0.2.0/24".parse().unwrap());
+	assert_eq!(inet.first_address(), Some("192.0.2.0".parse().unwrap()));
+	assert_eq!(inet.last(), Some("192.0.2.255/24".parse().unwrap()));
+	assert!(!inet.contains(&"192.0.3.1".parse().unwrap()));
+	assert_eq!(
+		Option::<IpInet>::from(inet),
+		Some("192.0.2.1/24".parse().unwrap())
+	);
+
+	assert_eq!(
+		AnyIpInet::new("2001:db8::1".parse().unwrap(), 129),
+		Err(crate::errors::NetworkLengthTooLongError::new(
+			129,
+			crate::Family::Ipv6
+		))
+	);
+	assert!("any/8".parse::<AnyIpInet>().is_err());
+}
+
+#[test]
+fn test_bits() {
+	let inet = "192.0.2.1/24".parse::<Ipv4Inet>().unwrap();
+	assert_eq!(inet.to_bits(), (0xc000_0201, 24));
+	assert_eq!(<(u32, u8)>::from(inet), (0xc000_0201, 24));
+	assert_eq!(Ipv4Inet::from_bits(0xc000_0201, 24), Ok(inet));
+	assert_eq!(Ipv4Inet::try_from((0xc000_0201, 24)), Ok(inet));
+	assert_eq!(Ipv4Inet::from_bits(0, 33).unwrap_err().network_length(), 33);
+
+	let inet = "2001:db8::1/32".parse::<Ipv6Inet>().unwrap();
+	assert_eq!(inet.to_bits(), (0x2001_0db8 << 96 | 1, 32));
+	assert_eq!(Ipv6Inet::try_from((0x2001_0db8 << 96 | 1, 32)), Ok(inet));
+
+	let inet = IpInet::V6(inet);
+	assert_eq!(inet.to_bits(), (0x2001_0db8 << 96 | 1, 32));
+	assert_eq!(
+		IpInet::from_bits(crate::Family::Ipv6, 0x2001_0db8 << 96 | 1, 32),
+		Ok(inet)
+	);
+	assert_eq!(
+		IpInet::from_bits(crate::Family::Ipv4, 1 << 32, 0),
+		Err(NetworkParseError::AddressBitsOutOfRange)
+	);
+}
@@ -1,9 +1,13 @@
+use core::net::IpAddr;
+
 use crate::{
 	internal_traits::PrivInetPair,
 	num::NumberOfAddresses,
 	Address,
 	Inet,
 	InetPair,
+	InetPermutedIterator,
+	IpInet,
 };
 
 /// Iterator type to iterate over a list of IP addresses within a network
@@ -19,17 +23,68 @@ impl<A: Address> InetIterator<A> {
 		}
 	}
 
+	pub(crate) const fn _empty() -> Self {
+		Self { state: None }
+	}
+
 	/// Iterate only over addresses (without network prefix length)
 	pub const fn addresses(self) -> InetAddressIterator<A> {
 		InetAddressIterator { inner: self }
 	}
+
+	/// Iterate over every `step`-th address
+	///
+	/// Like [`Iterator::step_by`], but the step can be larger than a
+	/// `usize` (useful in IPv6 networks).
+	///
+	/// # Panics
+	///
+	/// Panics if `step` is zero.
+	pub fn stride(self, step: u128) -> InetStrideIterator<A> {
+		assert!(step != 0, "stride step must not be zero");
+		InetStrideIterator { inner: self, step }
+	}
+
+	/// Iterate over the remaining addresses in a pseudo-random order
+	/// determined by `seed`
+	///
+	/// See [`InetPermutedIterator`].
+	pub fn permuted(self, seed: u64) -> InetPermutedIterator<A> {
+		InetPermutedIterator::new(self.state, seed, self.remaining())
+	}
+
+	/// Iterate over a pseudo-random sample of (at most) `k` distinct
+	/// addresses determined by `seed`
+	///
+	/// Same as the first `k` addresses of [`permuted`](Self::permuted).
+	pub fn sample(self, k: u128, seed: u64) -> InetPermutedIterator<A> {
+		let limit = match self.remaining() {
+			NumberOfAddresses::Count(c) if c < k => NumberOfAddresses::Count(c),
+			NumberOfAddresses::Count(_) | NumberOfAddresses::MaxIpv6Addresses => {
+				NumberOfAddresses::Count(k)
+			},
+		};
+		InetPermutedIterator::new(self.state, seed, limit)
+	}
+
+	/// Exact number of remaining addresses
+	///
+	/// Unlike [`Iterator::count`] this doesn't consume the iterator, and
+	/// it also works if the number doesn't fit into a `usize` (an IPv6
+	/// network can have up to 2<sup>128</sup> addresses).
+	pub fn remaining(&self) -> NumberOfAddresses {
+		match &self.state {
+			None => NumberOfAddresses::Count(0),
+			Some(state) => state._covered_addresses(),
+		}
+	}
 }
 
 impl<A: Address> Iterator for InetIterator<A> {
 	type Item = A::Inet;
 
 	fn next(&mut self) -> Option<Self::Item> {
-		let state = self.state.as_mut().take()?;
+		let state = self.state.as_mut()?;
 		let res = state.first();
 		if !state._inc_first() {
 			self.state = None;
@@ -38,12 +93,8 @@ impl<A: Address> Iterator for InetIterator<A> {
 	}
 
 	fn size_hint(&self) -> (usize, Option<usize>) {
-		let state = match &self.state {
-			None => return (0, Some(0)),
-			Some(state) => state,
-		};
-		match state._covered_addresses() {
-			NumberOfAddresses::MaxIpv6Addresses => (0, None),
+		match self.remaining() {
+			NumberOfAddresses::MaxIpv6Addresses => (usize::MAX, None),
 			NumberOfAddresses::Count(c) => {
 				if c > (usize::MAX as u128) {
 					(usize::MAX, None)
@@ -61,27 +112,186 @@ impl<A: Address> Iterator for InetIterator<A> {
 	{
 		self.size_hint().1.expect("iterator count overflow")
 	}
+
+	fn last(mut self) -> Option<Self::Item>
+	where
+		Self: Sized,
+	{
+		self.next_back()
+	}
+
+	/// Skips `n` addresses in constant time (which also makes
+	/// [`Iterator::step_by`] efficient).
+	fn nth(&mut self, n: usize) -> Option<Self::Item> {
+		let state = self.state.as_mut()?;
+		if !state._inc_first_by(n as u128) {
+			self.state = None;
+			return None;
+		}
+		self.next()
+	}
 }
 
 impl<A: Address> core::iter::DoubleEndedIterator for InetIterator<A> {
 	fn next_back(&mut self) -> Option<Self::Item> {
-		let state = self.state.as_mut().take()?;
+		let state = self.state.as_mut()?;
 		let res = state.second();
 		if !state._dec_second() {
 			self.state = None;
 		}
 		Some(res)
 	}
+
+	/// Skips `n` addresses from the end in constant time.
+	fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+		let state = self.state.as_mut()?;
+		if !state._dec_second_by(n as u128) {
+			self.state = None;
+			return None;
+		}
+		self.next_back()
+	}
 }
 
 impl<A: Address> core::iter::FusedIterator for InetIterator<A> {}
 
+/// Iterator type to iterate over all addresses of an [`AnyIpCidr`]
+///
+/// For [`AnyIpCidr::Any`] first iterates over the IPv4 address space,
+/// then over the IPv6 address space (both with network length `0`).
+///
+/// Created by [`AnyIpCidr::iter`].
+///
+/// [`AnyIpCidr`]: crate::AnyIpCidr
+/// [`AnyIpCidr::Any`]: crate::AnyIpCidr::Any
+/// [`AnyIpCidr::iter`]: crate::AnyIpCidr::iter
+#[derive(Clone, Copy, Debug)]
+pub struct AnyIpCidrIterator {
+	first: InetIterator<IpAddr>,
+	second: InetIterator<IpAddr>,
+}
+
+impl AnyIpCidrIterator {
+	pub(crate) const fn _new(first: InetIterator<IpAddr>, second: InetIterator<IpAddr>) -> Self {
+		Self { first, second }
+	}
+}
+
+impl Iterator for AnyIpCidrIterator {
+	type Item = IpInet;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.first.next().or_else(|| self.second.next())
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let (first_low, first_high) = self.first.size_hint();
+		let (second_low, second_high) = self.second.size_hint();
+		let high = match (first_high, second_high) {
+			(Some(a), Some(b)) => a.checked_add(b),
+			_ => None,
+		};
+		(first_low.saturating_add(second_low), high)
+	}
+
+	fn count(self) -> usize
+	where
+		Self: Sized,
+	{
+		self.size_hint().1.expect("iterator count overflow")
+	}
+
+	fn last(mut self) -> Option<Self::Item>
+	where
+		Self: Sized,
+	{
+		self.next_back()
+	}
+
+	/// Skips `n` addresses in constant time.
+	fn nth(&mut self, n: usize) -> Option<Self::Item> {
+		match self.first.remaining() {
+			NumberOfAddresses::Count(c) if c <= n as u128 => {
+				self.first = InetIterator::_empty();
+				self.second.nth(n - c as usize)
+			},
+			_ => self.first.nth(n),
+		}
+	}
+}
+
+impl core::iter::DoubleEndedIterator for AnyIpCidrIterator {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.second.next_back().or_else(|| self.first.next_back())
+	}
+
+	/// Skips `n` addresses from the end in constant time.
+	fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+		match self.second.remaining() {
+			NumberOfAddresses::Count(c) if c <= n as u128 => {
+				self.second = InetIterator::_empty();
+				self.first.nth_back(n - c as usize)
+			},
+			_ => self.second.nth_back(n),
+		}
+	}
+}
+
+impl core::iter::FusedIterator for AnyIpCidrIterator {}
+
+/// Iterator over every n-th address in a list of IP addresses within a
+/// network
+///
+/// Created by [`InetIterator::stride`].
+#[derive(Clone, Copy, Debug)]
+pub struct InetStrideIterator<A: Address> {
+	inner: InetIterator<A>,
+	step: u128,
+}
+
+impl<A: Address> Iterator for InetStrideIterator<A> {
+	type Item = A::Inet;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let state = self.inner.state.as_mut()?;
+		let res = state.first();
+		if !state._inc_first_by(self.step) {
+			self.inner.state = None;
+		}
+		Some(res)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		// ceil(remaining / step)
+		let remaining = match self.inner.remaining() {
+			NumberOfAddresses::Count(0) => Some(0),
+			NumberOfAddresses::Count(c) => Some((c - 1) / self.step + 1),
+			NumberOfAddresses::MaxIpv6Addresses => (u128::MAX / self.step).checked_add(1),
+		};
+		match remaining {
+			Some(c) if c <= (usize::MAX as u128) => (c as usize, Some(c as usize)),
+			_ => (usize::MAX, None),
+		}
+	}
+}
+
+impl<A: Address> core::iter::FusedIterator for InetStrideIterator<A> {}
+
 /// Iterator type to iterate over a list of IP addresses in a network
 #[derive(Clone, Copy, Debug)]
 pub struct InetAddressIterator<A: Address> {
 	inner: InetIterator<A>,
 }
 
+impl<A: Address> InetAddressIterator<A> {
+	/// Exact number of remaining addresses
+	///
+	/// See [`InetIterator::remaining`].
+	pub fn remaining(&self) -> NumberOfAddresses {
+		self.inner.remaining()
+	}
+}
+
 impl<A: Address> Iterator for InetAddressIterator<A> {
 	type Item = A;
 
@@ -99,12 +309,30 @@ impl<A: Address> Iterator for InetAddressIterator<A> {
 	{
 		self.inner.count()
 	}
+
+	fn last(self) -> Option<Self::Item>
+	where
+		Self: Sized,
+	{
+		Some(self.inner.last()?.address())
+	}
+
+	/// Skips `n` addresses in constant time (which also makes
+	/// [`Iterator::step_by`] efficient).
+	fn nth(&mut self, n: usize) -> Option<Self::Item> {
+		Some(self.inner.nth(n)?.address())
+	}
 }
 
 impl<A: Address> core::iter::DoubleEndedIterator for InetAddressIterator<A> {
 	fn next_back(&mut self) -> Option<Self::Item> {
 		Some(self.inner.next_back()?.address())
 	}
+
+	/// Skips `n` addresses from the end in constant time.
+	fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+		Some(self.inner.nth_back(n)?.address())
+	}
 }
 
 impl<A: Address> core::iter::FusedIterator for InetAddressIterator<A> {}
@@ -115,6 +343,7 @@ mod tests {
 		IpCidr,
 		Ipv4Cidr,
 		Ipv6Cidr,
+		NumberOfAddresses,
 	};
 	use core::net::{
 		IpAddr,
@@ -234,4 +463,109 @@ mod tests {
 			],
 		);
 	}
+
+	#[test]
+	fn nth_v4() {
+		let cidr = "192.0.2.0/24".parse::<Ipv4Cidr>().unwrap();
+		let mut iter = cidr.iter().addresses();
+		assert_eq!(iter.nth(1), Some(Ipv4Addr::new(192, 0, 2, 1)));
+		assert_eq!(iter.nth(9), Some(Ipv4Addr::new(192, 0, 2, 11)));
+		assert_eq!(iter.nth_back(5), Some(Ipv4Addr::new(192, 0, 2, 250)));
+		assert_eq!(iter.size_hint(), (238, Some(238)));
+		assert_eq!(iter.remaining(), NumberOfAddresses::Count(238));
+		assert_eq!(iter.nth(237), Some(Ipv4Addr::new(192, 0, 2, 249)));
+		assert_eq!(iter.remaining(), NumberOfAddresses::Count(0));
+		assert_eq!(iter.next(), None);
+
+		let mut iter = cidr.iter().addresses();
+		assert_eq!(iter.nth(256), None);
+		assert_eq!(iter.next(), None);
+
+		let mut iter = cidr.iter().addresses();
+		assert_eq!(iter.nth_back(256), None);
+		assert_eq!(iter.next_back(), None);
+
+		assert_eq!(cidr.iter().count(), 256);
+		assert_eq!(
+			cidr.iter().addresses().last(),
+			Some(Ipv4Addr::new(192, 0, 2, 255))
+		);
+		check_list_iter(
+			[
+				Ipv4Addr::new(192, 0, 2, 0),
+				Ipv4Addr::new(192, 0, 2, 100),
+				Ipv4Addr::new(192, 0, 2, 200),
+			],
+			cidr.iter().addresses().step_by(100),
+		);
+	}
+
+	#[test]
+	#[cfg(target_pointer_width = "64")]
+	fn nth_v6() {
+		let cidr = "2001:db8::/64".parse::<Ipv6Cidr>().unwrap();
+		let mut iter = cidr.iter().addresses();
+		assert_eq!(iter.remaining(), NumberOfAddresses::Count(1 << 64),);
+		assert_eq!(iter.size_hint(), (usize::MAX, None));
+		assert_eq!(
+			iter.nth(usize::MAX),
+			Some("2001:db8::ffff:ffff:ffff:ffff".parse().unwrap())
+		);
+		assert_eq!(iter.next(), None);
+
+		let mut iter = cidr.iter().addresses().step_by(1 << 62);
+		assert_eq!(iter.next(), Some("2001:db8::".parse().unwrap()));
+		assert_eq!(iter.next(), Some("2001:db8::4000:0:0:0".parse().unwrap()));
+		assert_eq!(iter.next(), Some("2001:db8::8000:0:0:0".parse().unwrap()));
+		assert_eq!(iter.next(), Some("2001:db8::c000:0:0:0".parse().unwrap()));
+		assert_eq!(iter.next(), None);
+
+		let cidr = "::/0".parse::<IpCidr>().unwrap();
+		let mut iter = cidr.iter().addresses();
+		assert_eq!(iter.remaining(), NumberOfAddresses::MaxIpv6Addresses);
+		assert_eq!(iter.size_hint(), (usize::MAX, None));
+		assert_eq!(
+			iter.nth_back(1),
+			Some(IpAddr::V6(Ipv6Addr::new(
+				0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xfffe
+			)))
+		);
+		assert_eq!(iter.remaining(), NumberOfAddresses::Count(u128::MAX - 1));
+		assert_eq!(
+			cidr.iter().last().map(|inet| inet.address()),
+			Some(IpAddr::V6(Ipv6Addr::new(
+				0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff
+			)))
+		);
+	}
+
+	#[test]
+	fn stride() {
+		let cidr = "192.0.2.0/24".parse::<Ipv4Cidr>().unwrap();
+		let iter = cidr.iter().stride(100);
+		assert_eq!(iter.size_hint(), (3, Some(3)));
+		check_list_iter(
+			[
+				Ipv4Addr::new(192, 0, 2, 0),
+				Ipv4Addr::new(192, 0, 2, 100),
+				Ipv4Addr::new(192, 0, 2, 200),
+			],
+			iter.map(|inet| inet.address()),
+		);
+		assert_eq!(cidr.iter().stride(256).count(), 1);
+
+		let cidr = "::/0".parse::<Ipv6Cidr>().unwrap();
+		let iter = cidr.iter().stride(1 << 126);
+		assert_eq!(iter.size_hint(), (4, Some(4)));
+		check_list_iter(
+			[
+				Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0),
+				Ipv6Addr::new(0x4000, 0, 0, 0, 0, 0, 0, 0),
+				Ipv6Addr::new(0x8000, 0, 0, 0, 0, 0, 0, 0),
+				Ipv6Addr::new(0xc000, 0, 0, 0, 0, 0, 0, 0),
+			],
+			iter.map(|inet| inet.address()),
+		);
+		assert_eq!(cidr.iter().stride(1).size_hint(), (usize::MAX, None));
+	}
 }
@@ -148,6 +148,20 @@ impl PrivInetPair for IpInetPair {
 			Self::V6(p) => p._dec_second(),
 		}
 	}
+
+	fn _inc_first_by(&mut self, n: u128) -> bool {
+		match self {
+			Self::V4(p) => p._inc_first_by(n),
+			Self::V6(p) => p._inc_first_by(n),
+		}
+	}
+
+	fn _dec_second_by(&mut self, n: u128) -> bool {
+		match self {
+			Self::V4(p) => p._dec_second_by(n),
+			Self::V6(p) => p._dec_second_by(n),
+		}
+	}
 }
 
 impl InetPair for IpInetPair {
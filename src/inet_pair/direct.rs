@@ -104,13 +104,19 @@ macro_rules! impl_inet_pair_for {
 			pub const fn iter(self) -> InetIterator<$addr> {
 				InetIterator::_new(self)
 			}
+
+			// number of addresses after `first` up to `second` (0 if
+			// `second` isn't after `first`)
+			const fn _distance(&self) -> u128 {
+				let first: $native = <$addr as PrivUnspecAddress>::_Tools::to_native(self.first);
+				let second: $native = <$addr as PrivUnspecAddress>::_Tools::to_native(self.second);
+				second.saturating_sub(first) as u128
+			}
 		}
 
 		impl PrivInetPair for $n {
 			fn _covered_addresses(&self) -> NumberOfAddresses {
-				let first: $native = <$addr as PrivUnspecAddress>::_Tools::to_native(self.first);
-				let second: $native = <$addr as PrivUnspecAddress>::_Tools::to_native(self.second);
-				NumberOfAddresses::count_from_distance((second - first) as u128)
+				NumberOfAddresses::count_from_distance(self._distance())
 			}
 
 			fn _inc_first(&mut self) -> bool {
@@ -132,6 +138,28 @@ macro_rules! impl_inet_pair_for {
 					false
 				}
 			}
+
+			fn _inc_first_by(&mut self, n: u128) -> bool {
+				if n <= self._distance() {
+					let current = <$addr as PrivUnspecAddress>::_Tools::to_native(self.first);
+					self.first =
+						<$addr as PrivUnspecAddress>::_Tools::from_native(current + n as $native);
+					true
+				} else {
+					false
+				}
+			}
+
+			fn _dec_second_by(&mut self, n: u128) -> bool {
+				if n <= self._distance() {
+					let current = <$addr as PrivUnspecAddress>::_Tools::to_native(self.second);
+					self.second =
+						<$addr as PrivUnspecAddress>::_Tools::from_native(current - n as $native);
+					true
+				} else {
+					false
+				}
+			}
 		}
 
 		impl InetPair for $n {
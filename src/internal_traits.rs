@@ -17,11 +17,18 @@ pub trait PrivCidr {}
 /// seal `Inet` trait
 pub trait PrivInet {}
 
+/// seal `NetworkLike` trait
+pub trait PrivNetworkLike {}
+
 /// seal `InetPair` trait
 pub trait PrivInetPair {
 	fn _covered_addresses(&self) -> NumberOfAddresses;
 	fn _inc_first(&mut self) -> bool;
 	fn _dec_second(&mut self) -> bool;
+	/// like `_inc_first`, but step `n` addresses
+	fn _inc_first_by(&mut self, n: u128) -> bool;
+	/// like `_dec_second`, but step `n` addresses
+	fn _dec_second_by(&mut self, n: u128) -> bool;
 }
 
 #[derive(Clone, Copy)]
@@ -142,6 +149,14 @@ impl Ipv4AddrTools {
 		}
 	*/
 
+	pub(crate) const fn _host_part(address: Ipv4Addr, prefix_len: u8) -> u128 {
+		(Self::to_native(address) & Self::native_host_mask(prefix_len)) as u128
+	}
+
+	pub(crate) const fn _distance(address: Ipv4Addr, other: Ipv4Addr) -> u128 {
+		Self::to_native(address).abs_diff(Self::to_native(other)) as u128
+	}
+
 	pub(crate) const fn _prefix_match(address: Ipv4Addr, other: Ipv4Addr, prefix_len: u8) -> bool {
 		let net_mask: u32 = !Self::native_host_mask(prefix_len);
 		(Self::to_native(address) & net_mask) == (Self::to_native(other) & net_mask)
@@ -283,6 +298,14 @@ impl Ipv6AddrTools {
 		}
 	*/
 
+	pub(crate) const fn _host_part(address: Ipv6Addr, prefix_len: u8) -> u128 {
+		Self::to_native(address) & Self::native_host_mask(prefix_len)
+	}
+
+	pub(crate) const fn _distance(address: Ipv6Addr, other: Ipv6Addr) -> u128 {
+		Self::to_native(address).abs_diff(Self::to_native(other))
+	}
+
 	pub(crate) const fn _prefix_match(address: Ipv6Addr, other: Ipv6Addr, prefix_len: u8) -> bool {
 		let net_mask: u128 = !Self::native_host_mask(prefix_len);
 		(Self::to_native(address) & net_mask) == (Self::to_native(other) & net_mask)
@@ -0,0 +1,288 @@
+use alloc::collections::BTreeMap;
+
+use crate::{
+	errors::AllocationError,
+	Address,
+	Cidr,
+	Inet,
+	InetPair,
+};
+
+// set of `u128` values stored as disjoint, non-adjacent inclusive ranges
+// (start => end)
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+struct RangeSet(BTreeMap<u128, u128>);
+
+impl RangeSet {
+	fn contains(&self, value: u128) -> bool {
+		match self.0.range(..=value).next_back() {
+			Some((_, &end)) => value <= end,
+			None => false,
+		}
+	}
+
+	// `value` must not be contained yet
+	fn insert(&mut self, value: u128) {
+		let mut start = value;
+		let mut end = value;
+		if let Some((&s, &e)) = self.0.range(..value).next_back() {
+			if e + 1 == value {
+				start = s;
+			}
+		}
+		if let Some(e) = value.checked_add(1).and_then(|next| self.0.remove(&next)) {
+			end = e;
+		}
+		self.0.insert(start, end);
+	}
+
+	// `value` must be contained
+	fn remove(&mut self, value: u128) {
+		let (&start, &end) = self.0.range(..=value).next_back().expect("contained value");
+		if start < value {
+			self.0.insert(start, value - 1);
+		} else {
+			self.0.remove(&start);
+		}
+		if value < end {
+			self.0.insert(value + 1, end);
+		}
+	}
+
+	// lowest value not contained
+	fn first_missing(&self) -> Option<u128> {
+		match self.0.first_key_value() {
+			Some((0, &end)) => end.checked_add(1),
+			_ => Some(0),
+		}
+	}
+}
+
+/// Allocator handing out single host addresses of a network (or an
+/// address range)
+///
+/// Always hands out the lowest free address.  Used addresses are stored
+/// as ranges, so (mostly) sequential allocations need little memory even
+/// in huge IPv6 networks.
+///
+/// Apart from allocated (or reserved) addresses some addresses can be
+/// excluded (e.g. network, broadcast and gateway addresses): they are
+/// never handed out and can't be released.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct HostAllocator<A: Address> {
+	range: A::InetPair,
+	// offsets (relative to the first address of `range`) not free
+	used: RangeSet,
+	excluded: RangeSet,
+	allocated: u128,
+}
+
+impl<A: Address> HostAllocator<A> {
+	/// Create new allocator for the addresses `range.first()..=range.second()`
+	pub fn new<P: InetPair<Address = A>>(range: P) -> Self {
+		Self {
+			// same type, but the compiler doesn't know
+			range: A::InetPair::new(range.first(), range.second()).expect("same network"),
+			used: RangeSet::default(),
+			excluded: RangeSet::default(),
+			allocated: 0,
+		}
+	}
+
+	/// Create new allocator for all addresses in the network
+	///
+	/// See [`for_hosts`](Self::for_hosts) to exclude the network and
+	/// broadcast addresses.
+	pub fn for_network<C: Cidr<Address = A>>(network: C) -> Self {
+		Self::new(A::InetPair::new(network.first(), network.last()).expect("same network"))
+	}
+
+	/// Create new allocator for the addresses usable for hosts in the
+	/// network (see [`Cidr::hosts`])
+	pub fn for_hosts<C: Cidr<Address = A>>(network: C) -> Self {
+		let mut hosts = network.hosts();
+		let first = hosts.next().expect("at least one host");
+		let second = hosts.next_back().unwrap_or(first);
+		Self::new(A::InetPair::new(first, second).expect("same network"))
+	}
+
+	/// Range of addresses managed by the allocator
+	pub fn range(&self) -> A::InetPair {
+		self.range
+	}
+
+	/// Allocate the lowest free address
+	pub fn allocate(&mut self) -> Result<A::Inet, AllocationError> {
+		let (offset, inet) = self
+			.used
+			.first_missing()
+			.and_then(|offset| Some((offset, self.inet_at(offset)?)))
+			.ok_or(AllocationError::Exhausted)?;
+		self.used.insert(offset);
+		self.allocated += 1;
+		Ok(inet)
+	}
+
+	/// Mark a specific address as allocated
+	///
+	/// Fails with [`AllocationError::NotInPool`] if the address is not
+	/// in the range, and with [`AllocationError::Overlapping`] if it is
+	/// not free.
+	pub fn reserve(&mut self, addr: A) -> Result<A::Inet, AllocationError> {
+		let offset = self.offset_of(addr)?;
+		if self.used.contains(offset) {
+			return Err(AllocationError::Overlapping);
+		}
+		self.used.insert(offset);
+		self.allocated += 1;
+		Ok(self.inet_at(offset).expect("valid offset"))
+	}
+
+	/// Exclude an address from allocation
+	///
+	/// Fails with [`AllocationError::NotInPool`] if the address is not
+	/// in the range, and with [`AllocationError::Overlapping`] if it is
+	/// allocated; excluding an address again is not an error.
+	pub fn exclude(&mut self, addr: A) -> Result<(), AllocationError> {
+		let offset = self.offset_of(addr)?;
+		if self.excluded.contains(offset) {
+			return Ok(());
+		}
+		if self.used.contains(offset) {
+			return Err(AllocationError::Overlapping);
+		}
+		self.used.insert(offset);
+		self.excluded.insert(offset);
+		Ok(())
+	}
+
+	/// Release an allocated (or reserved) address
+	///
+	/// Fails with [`AllocationError::NotAllocated`] if the address is not
+	/// allocated (including excluded addresses), and with
+	/// [`AllocationError::NotInPool`] if it is not in the range.
+	pub fn release(&mut self, addr: A) -> Result<(), AllocationError> {
+		let offset = self.offset_of(addr)?;
+		if !self.used.contains(offset) || self.excluded.contains(offset) {
+			return Err(AllocationError::NotAllocated);
+		}
+		self.used.remove(offset);
+		self.allocated -= 1;
+		Ok(())
+	}
+
+	/// Whether the address is in the range and neither allocated nor
+	/// excluded
+	pub fn is_free(&self, addr: A) -> bool {
+		match self.offset_of(addr) {
+			Ok(offset) => !self.used.contains(offset),
+			Err(_) => false,
+		}
+	}
+
+	/// Number of allocated (and reserved) addresses
+	pub fn allocated_count(&self) -> u128 {
+		self.allocated
+	}
+
+	fn offset_of(&self, addr: A) -> Result<u128, AllocationError> {
+		let first = self.range.first();
+		let second = self.range.second();
+		if !first.contains(&addr) || addr < first.address() || addr > second.address() {
+			return Err(AllocationError::NotInPool);
+		}
+		let inet = A::Inet::new(addr, first.network_length()).expect("valid network length");
+		Ok(inet.offset_in_network() - first.offset_in_network())
+	}
+
+	fn inet_at(&self, offset: u128) -> Option<A::Inet> {
+		let inet = self.range.first().checked_add(offset)?;
+		(inet.address() <= self.range.second().address()).then_some(inet)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		IpCidr,
+		Ipv4Cidr,
+		Ipv6Cidr,
+	};
+
+	#[test]
+	fn range_set() {
+		let mut s = RangeSet::default();
+		assert_eq!(s.first_missing(), Some(0));
+		s.insert(0);
+		s.insert(2);
+		assert_eq!(s.first_missing(), Some(1));
+		s.insert(1);
+		assert_eq!(s.0.len(), 1);
+		assert_eq!(s.first_missing(), Some(3));
+		s.remove(1);
+		assert_eq!(s.0.len(), 2);
+		assert!(s.contains(0) && !s.contains(1) && s.contains(2));
+		s.insert(u128::MAX);
+		assert!(s.contains(u128::MAX));
+	}
+
+	#[test]
+	fn allocate() {
+		let mut a = HostAllocator::for_hosts("192.0.2.0/29".parse::<Ipv4Cidr>().unwrap());
+		a.exclude("192.0.2.1".parse().unwrap()).unwrap();
+		assert_eq!(a.allocate(), Ok("192.0.2.2/29".parse().unwrap()));
+		assert_eq!(
+			a.reserve("192.0.2.4".parse().unwrap()),
+			Ok("192.0.2.4/29".parse().unwrap())
+		);
+		assert_eq!(a.allocate(), Ok("192.0.2.3/29".parse().unwrap()));
+		assert_eq!(a.allocate(), Ok("192.0.2.5/29".parse().unwrap()));
+		assert_eq!(a.allocate(), Ok("192.0.2.6/29".parse().unwrap()));
+		assert_eq!(a.allocate(), Err(AllocationError::Exhausted));
+		assert_eq!(a.allocated_count(), 5);
+
+		a.release("192.0.2.3".parse().unwrap()).unwrap();
+		assert!(a.is_free("192.0.2.3".parse().unwrap()));
+		assert_eq!(a.allocate(), Ok("192.0.2.3/29".parse().unwrap()));
+	}
+
+	#[test]
+	fn errors() {
+		let mut a = HostAllocator::for_hosts("192.0.2.0/29".parse::<IpCidr>().unwrap());
+		let broadcast = "192.0.2.7".parse().unwrap();
+		assert_eq!(a.reserve(broadcast), Err(AllocationError::NotInPool));
+		assert_eq!(
+			a.reserve("2001:db8::1".parse().unwrap()),
+			Err(AllocationError::NotInPool)
+		);
+		assert!(!a.is_free(broadcast));
+
+		let gateway = "192.0.2.1".parse().unwrap();
+		a.exclude(gateway).unwrap();
+		a.exclude(gateway).unwrap();
+		assert_eq!(a.release(gateway), Err(AllocationError::NotAllocated));
+		assert_eq!(a.reserve(gateway), Err(AllocationError::Overlapping));
+		let host = a.allocate().unwrap().address();
+		assert_eq!(a.exclude(host), Err(AllocationError::Overlapping));
+		a.release(host).unwrap();
+		assert_eq!(a.release(host), Err(AllocationError::NotAllocated));
+	}
+
+	#[test]
+	fn huge_network() {
+		let mut a = HostAllocator::for_network("::/0".parse::<Ipv6Cidr>().unwrap());
+		let last = "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff".parse().unwrap();
+		a.reserve(last).unwrap();
+		for _ in 0..1000 {
+			a.allocate().unwrap();
+		}
+		assert_eq!(a.used.0.len(), 2);
+		assert_eq!(a.allocate(), Ok("::3e8/0".parse().unwrap()));
+
+		let mut a = HostAllocator::for_hosts("2001:db8::/127".parse::<Ipv6Cidr>().unwrap());
+		assert_eq!(a.allocate(), Ok("2001:db8::/127".parse().unwrap()));
+		assert_eq!(a.allocate(), Ok("2001:db8::1/127".parse().unwrap()));
+		assert_eq!(a.allocate(), Err(AllocationError::Exhausted));
+	}
+}
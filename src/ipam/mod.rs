@@ -0,0 +1,112 @@
+//! IP address management: allocators handing out subnets of pools
+//!
+//! [`SubnetAllocator`] manages one or more pools (networks) and hands
+//! out aligned subnets of requested network lengths; released subnets
+//! are merged with their free "buddy" (the other half of the parent
+//! network) again.
+//!
+//! [`HostAllocator`] hands out single host addresses of a network (or
+//! an address range), e.g. for DHCP.
+//!
+//! [`plan_subnets`] computes a packed variable-length subnet (VLSM) plan
+//! of a network for a list of requirements.
+//!
+//! ```
+//! use cidr::{ipam::SubnetAllocator, Family, IpCidr};
+//!
+//! let mut allocator = SubnetAllocator::default();
+//! allocator.add_pool("10.0.0.0/16".parse().unwrap()).unwrap();
+//! let a = allocator.allocate(Family::Ipv4, 24).unwrap();
+//! let b = allocator.allocate(Family::Ipv4, 26).unwrap();
+//! assert_eq!(a, "10.0.0.0/24".parse::<IpCidr>().unwrap());
+//! assert_eq!(b, "10.0.1.0/26".parse::<IpCidr>().unwrap());
+//! allocator.release(a).unwrap();
+//! ```
+//!
+//! Requires the `alloc` feature.
+
+pub use self::{
+	host::HostAllocator,
+	plan::{
+		plan_subnets,
+		SubnetPlan,
+		SubnetSize,
+	},
+	subnet::{
+		AllocationStrategy,
+		SubnetAllocator,
+	},
+};
+
+mod host;
+mod plan;
+mod subnet;
+
+use crate::IpCidr;
+
+// network with length `len` containing `cidr`; `len` must not be
+// longer than the network length of `cidr`
+fn supernet(cidr: IpCidr, len: u8) -> IpCidr {
+	let (bits, _) = cidr.to_bits();
+	let host_bits = (cidr.family().len() - len) as u32;
+	let mask = u128::MAX.checked_shl(host_bits).unwrap_or(0);
+	IpCidr::from_bits(cidr.family(), bits & mask, len).expect("valid supernet")
+}
+
+// the two halves of a network (`None` for host addresses)
+fn halves(cidr: IpCidr) -> Option<(IpCidr, IpCidr)> {
+	if cidr.is_host_address() {
+		return None;
+	}
+	let (bits, len) = cidr.to_bits();
+	let len = len + 1;
+	let half = 1u128 << (cidr.family().len() - len);
+	let lower = IpCidr::from_bits(cidr.family(), bits, len).expect("valid subnet");
+	let upper = IpCidr::from_bits(cidr.family(), bits | half, len).expect("valid subnet");
+	Some((lower, upper))
+}
+
+// the other half of the parent network (`None` for `/0`)
+fn sibling(cidr: IpCidr) -> Option<IpCidr> {
+	let (bits, len) = cidr.to_bits();
+	if len == 0 {
+		return None;
+	}
+	let half = 1u128 << (cidr.family().len() - len);
+	Some(IpCidr::from_bits(cidr.family(), bits ^ half, len).expect("valid sibling"))
+}
+
+// number of addresses in the network (approximated for large networks)
+fn size(cidr: &IpCidr) -> f64 {
+	let host_bits = (cidr.family().len() - cidr.network_length()) as u32;
+	if host_bits == 128 {
+		u128::MAX as f64
+	} else {
+		(1u128 << host_bits) as f64
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn arithmetic() {
+		let c: IpCidr = "10.1.2.0/24".parse().unwrap();
+		assert_eq!(supernet(c, 8), "10.0.0.0/8".parse().unwrap());
+		assert_eq!(supernet(c, 0), "0.0.0.0/0".parse().unwrap());
+		assert_eq!(supernet(c, 24), c);
+		assert_eq!(
+			halves(c),
+			Some((
+				"10.1.2.0/25".parse().unwrap(),
+				"10.1.2.128/25".parse().unwrap()
+			))
+		);
+		assert_eq!(halves("::1/128".parse().unwrap()), None);
+		assert_eq!(sibling(c), Some("10.1.3.0/24".parse().unwrap()));
+		assert_eq!(sibling("::/0".parse().unwrap()), None);
+		assert_eq!(size(&c), 256.0);
+		assert_eq!(size(&"::/0".parse().unwrap()), u128::MAX as f64);
+	}
+}
@@ -0,0 +1,388 @@
+use alloc::vec::Vec;
+
+use crate::{
+	errors::{
+		AllocationError,
+		NetworkLengthTooLongError,
+		PlanError,
+	},
+	ipam::{
+		AllocationStrategy,
+		SubnetAllocator,
+	},
+	Cidr,
+	Family,
+	Inet,
+	IpCidr,
+};
+
+// number of host addresses (see `Cidr::hosts`) in a network of the
+// given length
+fn usable_hosts(family: Family, len: u8) -> u128 {
+	let host_bits = (family.len() - len) as u32;
+	match (family, host_bits) {
+		(_, 0) => 1,
+		(_, 1) => 2,
+		// network and broadcast address
+		(Family::Ipv4, _) => (1 << host_bits) - 2,
+		// subnet-router anycast address
+		(Family::Ipv6, 128) => u128::MAX,
+		(Family::Ipv6, _) => (1 << host_bits) - 1,
+	}
+}
+
+// number of addresses a requirement needs minus one (so the size of
+// a /0 IPv6 network fits)
+fn required_addresses_minus_one(family: Family, size: SubnetSize) -> u128 {
+	match size.network_length(family) {
+		Some(len) => u128::MAX
+			.checked_shr(128 - (family.len() - len) as u32)
+			.unwrap_or(0),
+		// too many hosts for the family; count network (and broadcast)
+		// addresses like for smaller networks
+		None => {
+			let SubnetSize::Hosts(hosts) = size else {
+				unreachable!("network length too long for family")
+			};
+			let reserved = match family {
+				Family::Ipv4 => 2,
+				Family::Ipv6 => 1,
+			};
+			hosts
+				.saturating_add(reserved)
+				.checked_next_power_of_two()
+				.map_or(u128::MAX, |addresses| addresses - 1)
+		},
+	}
+}
+
+// number of addresses missing to fit blocks with the given sizes (minus
+// one) into `available` (minus one) addresses (saturating)
+fn missing_addresses(available: u128, sizes: impl Iterator<Item = u128>) -> u128 {
+	// remaining addresses minus one; `None` if nothing remains
+	let mut remaining = Some(available);
+	let mut missing = 0u128;
+	for size in sizes {
+		match remaining {
+			Some(r) if size <= r => remaining = (r - size).checked_sub(1),
+			Some(r) => {
+				missing = missing.saturating_add(size - r);
+				remaining = None;
+			},
+			None => missing = missing.saturating_add(size).saturating_add(1),
+		}
+	}
+	missing
+}
+
+/// Size of a subnet required by [`plan_subnets`]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum SubnetSize {
+	/// Subnet with at least the given number of addresses usable for
+	/// hosts (see [`Cidr::hosts`])
+	Hosts(u128),
+	/// Subnet with the given network length
+	NetworkLength(u8),
+}
+
+impl SubnetSize {
+	/// Network length of the smallest subnet of the family satisfying
+	/// the requirement
+	///
+	/// Returns `None` if too many hosts are required or the network
+	/// length is too long for the family.
+	pub fn network_length(&self, family: Family) -> Option<u8> {
+		match *self {
+			Self::Hosts(hosts) => (0..=family.len())
+				.rev()
+				.find(|&len| usable_hosts(family, len) >= hosts),
+			Self::NetworkLength(len) => (len <= family.len()).then_some(len),
+		}
+	}
+}
+
+/// Result of [`plan_subnets`]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SubnetPlan<C, N> {
+	subnets: Vec<(N, C)>,
+	free: Vec<C>,
+}
+
+impl<C, N> SubnetPlan<C, N> {
+	/// Planned subnets with the name of their requirement (in the order
+	/// of the requirements)
+	pub fn subnets(&self) -> &[(N, C)] {
+		&self.subnets
+	}
+
+	/// Remaining free space as largest possible networks (ordered by
+	/// address)
+	pub fn free_blocks(&self) -> &[C] {
+		&self.free
+	}
+
+	/// Split into planned subnets and free blocks
+	pub fn into_parts(self) -> (Vec<(N, C)>, Vec<C>) {
+		(self.subnets, self.free)
+	}
+}
+
+/// Plan variable-length subnets (VLSM) of a parent network
+///
+/// Subnets are assigned largest first (requirements of the same size in
+/// the given order), each directly following the previous one; as all
+/// subnets are aligned powers of two this packing leaves no gaps, and
+/// a plan fails only if the required addresses exceed the parent
+/// network.
+///
+/// Fails with [`PlanError::DoesNotFit`] naming the (index of the) first
+/// requirement that didn't fit and the number of addresses missing to
+/// fit all requirements, and with [`PlanError::NetworkLengthTooLong`]
+/// for a requirement with a network length too long for the family.
+///
+/// ```
+/// use cidr::{
+///     ipam::{plan_subnets, SubnetSize},
+///     Ipv4Cidr,
+/// };
+///
+/// let parent: Ipv4Cidr = "192.0.2.0/24".parse().unwrap();
+/// let plan = plan_subnets(
+///     parent,
+///     [
+///         ("office", SubnetSize::Hosts(50)),
+///         ("servers", SubnetSize::Hosts(100)),
+///         ("uplink", SubnetSize::NetworkLength(31)),
+///     ],
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     plan.subnets(),
+///     [
+///         ("office", "192.0.2.128/26".parse().unwrap()),
+///         ("servers", "192.0.2.0/25".parse().unwrap()),
+///         ("uplink", "192.0.2.192/31".parse().unwrap()),
+///     ]
+/// );
+/// ```
+pub fn plan_subnets<C, N, I>(parent: C, requirements: I) -> Result<SubnetPlan<C, N>, PlanError>
+where
+	C: Cidr + Into<IpCidr>,
+	I: IntoIterator<Item = (N, SubnetSize)>,
+{
+	let family = parent.family();
+	let requirements: Vec<(N, SubnetSize)> = requirements.into_iter().collect();
+
+	let mut order = Vec::with_capacity(requirements.len());
+	for (index, &(_, size)) in requirements.iter().enumerate() {
+		let len = size.network_length(family);
+		if let (None, SubnetSize::NetworkLength(len)) = (len, size) {
+			return Err(PlanError::NetworkLengthTooLong {
+				requirement: index,
+				error: NetworkLengthTooLongError::new(len as usize, family),
+			});
+		}
+		order.push((required_addresses_minus_one(family, size), index, len));
+	}
+	// largest first, then in the given order
+	order.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+	let parent_block: IpCidr = parent.into();
+	let mut allocator = SubnetAllocator::new(AllocationStrategy::FirstFit);
+	allocator.add_pool(parent_block).expect("single pool");
+	let mut subnets = Vec::with_capacity(requirements.len());
+	subnets.resize(requirements.len(), None);
+	for &(_, index, len) in &order {
+		// `None`: too many hosts for any network of the family
+		let result = match len {
+			Some(len) => allocator.allocate(family, len),
+			None => Err(AllocationError::Exhausted),
+		};
+		match result {
+			Ok(subnet) => subnets[index] = Some(subnet),
+			Err(AllocationError::Exhausted) => {
+				let available = required_addresses_minus_one(
+					family,
+					SubnetSize::NetworkLength(parent.network_length()),
+				);
+				let sizes = order.iter().map(|&(addresses, _, _)| addresses);
+				return Err(PlanError::DoesNotFit {
+					requirement: index,
+					missing: missing_addresses(available, sizes),
+				});
+			},
+			Err(e) => unreachable!("allocation failed: {}", e),
+		}
+	}
+
+	let (parent_bits, _) = parent_block.to_bits();
+	let from_block = |block: IpCidr| -> C {
+		let (bits, len) = block.to_bits();
+		let addr = parent
+			.first()
+			.checked_add(bits - parent_bits)
+			.expect("block within parent")
+			.address();
+		C::new(addr, len).expect("aligned block")
+	};
+	let subnets = requirements
+		.into_iter()
+		.zip(subnets)
+		.map(|((name, _), subnet)| (name, from_block(subnet.expect("planned subnet"))))
+		.collect();
+	let mut free: Vec<C> = allocator.free_blocks().map(from_block).collect();
+	free.sort_by_key(|block| block.first_address());
+	Ok(SubnetPlan { subnets, free })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		Ipv4Cidr,
+		Ipv6Cidr,
+	};
+
+	#[test]
+	fn host_counts() {
+		for len in 0..=32 {
+			let c = Ipv4Cidr::new("0.0.0.0".parse().unwrap(), len).unwrap();
+			let hosts = c.hosts().count() as u128;
+			assert_eq!(usable_hosts(Family::Ipv4, len), hosts);
+			// a /31 has as many hosts as a /30
+			let expected = if len == 30 { 31 } else { len };
+			assert_eq!(
+				SubnetSize::Hosts(hosts).network_length(Family::Ipv4),
+				Some(expected),
+				"/{}",
+				len
+			);
+		}
+		assert_eq!(usable_hosts(Family::Ipv6, 64), (1 << 64) - 1);
+		assert_eq!(usable_hosts(Family::Ipv6, 127), 2);
+		assert_eq!(SubnetSize::Hosts(0).network_length(Family::Ipv6), Some(128));
+		assert_eq!(
+			SubnetSize::Hosts(1 << 32).network_length(Family::Ipv4),
+			None
+		);
+		assert_eq!(
+			SubnetSize::NetworkLength(33).network_length(Family::Ipv4),
+			None
+		);
+	}
+
+	#[test]
+	fn plan() {
+		let parent: Ipv4Cidr = "10.0.0.0/22".parse().unwrap();
+		let (subnets, free) = plan_subnets(
+			parent,
+			[
+				("a", SubnetSize::Hosts(20)),
+				("b", SubnetSize::Hosts(200)),
+				("c", SubnetSize::NetworkLength(30)),
+				("d", SubnetSize::Hosts(30)),
+			],
+		)
+		.unwrap()
+		.into_parts();
+		let cidr = |s: &str| s.parse::<Ipv4Cidr>().unwrap();
+		assert_eq!(
+			subnets,
+			[
+				("a", cidr("10.0.1.0/27")),
+				("b", cidr("10.0.0.0/24")),
+				("c", cidr("10.0.1.64/30")),
+				("d", cidr("10.0.1.32/27")),
+			]
+		);
+		assert_eq!(
+			free,
+			[
+				cidr("10.0.1.68/30"),
+				cidr("10.0.1.72/29"),
+				cidr("10.0.1.80/28"),
+				cidr("10.0.1.96/27"),
+				cidr("10.0.1.128/25"),
+				cidr("10.0.2.0/23"),
+			]
+		);
+
+		let plan = plan_subnets(parent, Vec::<((), SubnetSize)>::new()).unwrap();
+		assert_eq!(plan.free_blocks(), [parent]);
+	}
+
+	#[test]
+	fn does_not_fit() {
+		let parent: Ipv4Cidr = "192.0.2.0/24".parse().unwrap();
+		assert_eq!(
+			plan_subnets(
+				parent,
+				[
+					("a", SubnetSize::Hosts(100)),
+					("b", SubnetSize::Hosts(100)),
+					("c", SubnetSize::NetworkLength(28)),
+				]
+			),
+			Err(PlanError::DoesNotFit {
+				requirement: 2,
+				missing: 16,
+			})
+		);
+		assert_eq!(
+			plan_subnets(parent, [("a", SubnetSize::Hosts(1 << 32))]),
+			Err(PlanError::DoesNotFit {
+				requirement: 0,
+				missing: (1 << 33) - 256,
+			})
+		);
+		assert!(matches!(
+			plan_subnets(parent, [("a", SubnetSize::NetworkLength(33))]),
+			Err(PlanError::NetworkLengthTooLong { requirement: 0, .. })
+		));
+
+		let parent: Ipv6Cidr = "2001:db8::/32".parse().unwrap();
+		assert_eq!(
+			plan_subnets(
+				parent,
+				[
+					("a", SubnetSize::NetworkLength(33)),
+					("b", SubnetSize::NetworkLength(31)),
+				]
+			),
+			Err(PlanError::DoesNotFit {
+				requirement: 1,
+				missing: (1 << 96) + (1 << 95),
+			})
+		);
+
+		// the required addresses overflow `u128`
+		let parent: Ipv6Cidr = "::/0".parse().unwrap();
+		assert_eq!(
+			plan_subnets(
+				parent,
+				[
+					("a", SubnetSize::Hosts(u128::MAX)),
+					("b", SubnetSize::Hosts(5)),
+				]
+			),
+			Err(PlanError::DoesNotFit {
+				requirement: 1,
+				missing: 8,
+			})
+		);
+		assert_eq!(
+			plan_subnets(
+				parent,
+				[
+					("a", SubnetSize::NetworkLength(1)),
+					("b", SubnetSize::NetworkLength(1)),
+					("c", SubnetSize::NetworkLength(0)),
+				]
+			),
+			Err(PlanError::DoesNotFit {
+				requirement: 0,
+				missing: u128::MAX,
+			})
+		);
+	}
+}
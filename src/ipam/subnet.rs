@@ -0,0 +1,336 @@
+use alloc::collections::BTreeSet;
+
+use crate::{
+	errors::{
+		AllocationError,
+		NetworkLengthTooLongError,
+	},
+	ipam::{
+		halves,
+		sibling,
+		size,
+		supernet,
+	},
+	Family,
+	IpCidr,
+};
+
+// key in the set of free blocks; orders by size (largest first) within
+// a family
+fn free_key(block: IpCidr) -> (Family, u8, IpCidr) {
+	(block.family(), block.network_length(), block)
+}
+
+/// Which free block [`SubnetAllocator::allocate`] splits
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub enum AllocationStrategy {
+	/// Use the smallest free block large enough (keeps large blocks
+	/// available); ties are broken by lowest address
+	#[default]
+	BestFit,
+	/// Use the free block with the lowest address large enough
+	FirstFit,
+}
+
+/// Allocator handing out subnets of one or more pools
+///
+/// All allocated subnets are aligned (they are networks after all);
+/// free space is tracked as a set of networks, and a free network is
+/// merged with its "buddy" (the other half of the parent network) if
+/// both are free, as long as the parent network is within a pool.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct SubnetAllocator {
+	strategy: AllocationStrategy,
+	pools: BTreeSet<IpCidr>,
+	// (family, network length, network) to find free blocks by size
+	free: BTreeSet<(Family, u8, IpCidr)>,
+	allocated: BTreeSet<IpCidr>,
+}
+
+impl SubnetAllocator {
+	/// Create new allocator (without pools) using the given strategy
+	pub const fn new(strategy: AllocationStrategy) -> Self {
+		Self {
+			strategy,
+			pools: BTreeSet::new(),
+			free: BTreeSet::new(),
+			allocated: BTreeSet::new(),
+		}
+	}
+
+	/// Strategy used by [`allocate`](Self::allocate)
+	pub const fn strategy(&self) -> AllocationStrategy {
+		self.strategy
+	}
+
+	/// Add a pool to allocate from
+	///
+	/// Fails with [`AllocationError::Overlapping`] if the pool overlaps
+	/// an existing pool.
+	pub fn add_pool(&mut self, pool: IpCidr) -> Result<(), AllocationError> {
+		let overlaps =
+			|p: &IpCidr| p.contains(&pool.first_address()) || pool.contains(&p.first_address());
+		if self.pools.iter().any(overlaps) {
+			return Err(AllocationError::Overlapping);
+		}
+		self.pools.insert(pool);
+		self.insert_free(pool);
+		Ok(())
+	}
+
+	/// Allocate a free subnet with the given family and network length
+	pub fn allocate(&mut self, family: Family, len: u8) -> Result<IpCidr, AllocationError> {
+		if len > family.len() {
+			return Err(NetworkLengthTooLongError::new(len as usize, family).into());
+		}
+		let block = match self.strategy {
+			AllocationStrategy::BestFit => (0..=len).rev().find_map(|l| self.first_free(family, l)),
+			AllocationStrategy::FirstFit => {
+				(0..=len).filter_map(|l| self.first_free(family, l)).min()
+			},
+		};
+		let block = block.ok_or(AllocationError::Exhausted)?;
+		self.free.remove(&free_key(block));
+		let subnet = IpCidr::new(block.first_address(), len).expect("aligned subnet");
+		self.split_off(block, subnet);
+		Ok(subnet)
+	}
+
+	/// Mark a specific network as allocated
+	///
+	/// Fails with [`AllocationError::Overlapping`] if (parts of) the
+	/// network are already allocated, and with
+	/// [`AllocationError::NotInPool`] if the network is not within a
+	/// pool.
+	pub fn reserve(&mut self, network: IpCidr) -> Result<(), AllocationError> {
+		let block = (0..=network.network_length())
+			.map(|l| supernet(network, l))
+			.find(|block| self.free.contains(&free_key(*block)));
+		let Some(block) = block else {
+			if self.pool_of(&network).is_some() {
+				return Err(AllocationError::Overlapping);
+			}
+			return Err(AllocationError::NotInPool);
+		};
+		self.free.remove(&free_key(block));
+		self.split_off(block, network);
+		Ok(())
+	}
+
+	/// Release an allocated (or reserved) network
+	///
+	/// Fails with [`AllocationError::NotAllocated`] if the network is
+	/// not allocated (releasing only part of an allocation is not
+	/// supported).
+	pub fn release(&mut self, network: IpCidr) -> Result<(), AllocationError> {
+		if !self.allocated.remove(&network) {
+			return Err(AllocationError::NotAllocated);
+		}
+		self.insert_free(network);
+		Ok(())
+	}
+
+	/// Whether the network is allocated (exactly, not as part of a
+	/// larger allocation)
+	pub fn is_allocated(&self, network: &IpCidr) -> bool {
+		self.allocated.contains(network)
+	}
+
+	/// Pools (ordered)
+	pub fn pools(&self) -> impl Iterator<Item = IpCidr> + '_ {
+		self.pools.iter().copied()
+	}
+
+	/// Allocated networks (ordered)
+	pub fn allocated(&self) -> impl Iterator<Item = IpCidr> + '_ {
+		self.allocated.iter().copied()
+	}
+
+	/// Free blocks (ordered by family, then largest blocks first)
+	pub fn free_blocks(&self) -> impl Iterator<Item = IpCidr> + '_ {
+		self.free.iter().map(|&(_, _, block)| block)
+	}
+
+	/// Fraction of pool addresses allocated (`0.0` without pools)
+	pub fn utilization(&self) -> f64 {
+		let total: f64 = self.pools.iter().map(size).sum();
+		if total == 0.0 {
+			return 0.0;
+		}
+		self.allocated.iter().map(size).sum::<f64>() / total
+	}
+
+	/// Fragmentation of the free space: `1.0 - largest free block / free
+	/// addresses` (`0.0` if nothing or only a single block is free)
+	pub fn fragmentation(&self) -> f64 {
+		let sizes = self.free.iter().map(|(_, _, block)| size(block));
+		let largest = sizes.clone().fold(0.0, f64::max);
+		let free: f64 = sizes.sum();
+		if free == 0.0 {
+			return 0.0;
+		}
+		1.0 - largest / free
+	}
+
+	fn first_free(&self, family: Family, len: u8) -> Option<IpCidr> {
+		let &(f, l, block) = self
+			.free
+			.range((family, len, family.whole_network())..)
+			.next()?;
+		(f == family && l == len).then_some(block)
+	}
+
+	fn pool_of(&self, network: &IpCidr) -> Option<IpCidr> {
+		(0..=network.network_length())
+			.map(|l| supernet(*network, l))
+			.find(|pool| self.pools.contains(pool))
+	}
+
+	// split (removed) free `block` until reaching `network` (contained in
+	// `block`), mark `network` allocated and the remaining halves free
+	fn split_off(&mut self, mut block: IpCidr, network: IpCidr) {
+		while block != network {
+			let (lower, upper) = halves(block).expect("network within block");
+			if lower.contains(&network.first_address()) {
+				self.free.insert(free_key(upper));
+				block = lower;
+			} else {
+				self.free.insert(free_key(lower));
+				block = upper;
+			}
+		}
+		self.allocated.insert(network);
+	}
+
+	// insert free block, merging it with its free buddy
+	fn insert_free(&mut self, mut block: IpCidr) {
+		while !self.pools.contains(&block) {
+			let buddy = sibling(block).expect("network within pool");
+			if !self.free.remove(&free_key(buddy)) {
+				break;
+			}
+			block = supernet(block, block.network_length() - 1);
+		}
+		self.free.insert(free_key(block));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use alloc::vec::Vec;
+
+	use super::*;
+
+	fn cidr(s: &str) -> IpCidr {
+		s.parse().unwrap()
+	}
+
+	#[test]
+	fn best_fit() {
+		let mut a = SubnetAllocator::default();
+		a.add_pool(cidr("10.0.0.0/24")).unwrap();
+		a.add_pool(cidr("2001:db8::/32")).unwrap();
+		assert_eq!(a.allocate(Family::Ipv4, 26), Ok(cidr("10.0.0.0/26")));
+		assert_eq!(a.allocate(Family::Ipv4, 25), Ok(cidr("10.0.0.128/25")));
+		// best fit uses the free /26, not a part of a larger block
+		assert_eq!(a.allocate(Family::Ipv4, 27), Ok(cidr("10.0.0.64/27")));
+		assert_eq!(a.allocate(Family::Ipv4, 27), Ok(cidr("10.0.0.96/27")));
+		assert_eq!(
+			a.allocate(Family::Ipv4, 32),
+			Err(AllocationError::Exhausted)
+		);
+		assert_eq!(a.allocate(Family::Ipv6, 48), Ok(cidr("2001:db8::/48")));
+		assert!(matches!(
+			a.allocate(Family::Ipv4, 33),
+			Err(AllocationError::NetworkLengthTooLongError(_))
+		));
+	}
+
+	#[test]
+	fn first_fit() {
+		let mut a = SubnetAllocator::new(AllocationStrategy::FirstFit);
+		a.add_pool(cidr("10.0.0.0/24")).unwrap();
+		a.reserve(cidr("10.0.0.64/26")).unwrap();
+		a.reserve(cidr("10.0.0.128/27")).unwrap();
+		// free: .0/26, .160/27, .192/26
+		assert_eq!(a.allocate(Family::Ipv4, 27), Ok(cidr("10.0.0.0/27")));
+
+		let mut a = SubnetAllocator::new(AllocationStrategy::BestFit);
+		a.add_pool(cidr("10.0.0.0/24")).unwrap();
+		a.reserve(cidr("10.0.0.64/26")).unwrap();
+		a.reserve(cidr("10.0.0.128/27")).unwrap();
+		assert_eq!(a.allocate(Family::Ipv4, 27), Ok(cidr("10.0.0.160/27")));
+	}
+
+	#[test]
+	fn reserve_release() {
+		let mut a = SubnetAllocator::default();
+		a.add_pool(cidr("192.0.2.0/24")).unwrap();
+		assert_eq!(
+			a.add_pool(cidr("192.0.2.128/25")),
+			Err(AllocationError::Overlapping)
+		);
+		assert_eq!(
+			a.add_pool(cidr("192.0.0.0/16")),
+			Err(AllocationError::Overlapping)
+		);
+
+		a.reserve(cidr("192.0.2.64/26")).unwrap();
+		assert!(a.is_allocated(&cidr("192.0.2.64/26")));
+		assert_eq!(
+			a.reserve(cidr("192.0.2.64/27")),
+			Err(AllocationError::Overlapping)
+		);
+		assert_eq!(
+			a.reserve(cidr("192.0.2.0/24")),
+			Err(AllocationError::Overlapping)
+		);
+		assert_eq!(
+			a.reserve(cidr("198.51.100.0/24")),
+			Err(AllocationError::NotInPool)
+		);
+		assert_eq!(
+			a.free_blocks().collect::<Vec<_>>(),
+			[cidr("192.0.2.128/25"), cidr("192.0.2.0/26")]
+		);
+
+		let b = a.allocate(Family::Ipv4, 28).unwrap();
+		assert_eq!(
+			a.release(cidr("192.0.2.64/27")),
+			Err(AllocationError::NotAllocated)
+		);
+		a.release(cidr("192.0.2.64/26")).unwrap();
+		a.release(b).unwrap();
+		// everything merged back into the pool
+		assert_eq!(a.free_blocks().collect::<Vec<_>>(), [cidr("192.0.2.0/24")]);
+		assert_eq!(a.allocated().count(), 0);
+	}
+
+	#[test]
+	fn no_merge_across_pools() {
+		let mut a = SubnetAllocator::default();
+		a.add_pool(cidr("10.0.0.0/25")).unwrap();
+		a.add_pool(cidr("10.0.0.128/25")).unwrap();
+		assert_eq!(a.free_blocks().count(), 2);
+		let b = a.allocate(Family::Ipv4, 25).unwrap();
+		a.release(b).unwrap();
+		assert_eq!(a.free_blocks().count(), 2);
+		assert_eq!(
+			a.allocate(Family::Ipv4, 24),
+			Err(AllocationError::Exhausted)
+		);
+	}
+
+	#[test]
+	fn statistics() {
+		let mut a = SubnetAllocator::default();
+		assert_eq!(a.utilization(), 0.0);
+		assert_eq!(a.fragmentation(), 0.0);
+		a.add_pool(cidr("10.0.0.0/24")).unwrap();
+		assert_eq!(a.fragmentation(), 0.0);
+		a.reserve(cidr("10.0.0.64/26")).unwrap();
+		assert_eq!(a.utilization(), 0.25);
+		// free: /25 (128) and /26 (64)
+		assert_eq!(a.fragmentation(), 1.0 - 128.0 / 192.0);
+	}
+}
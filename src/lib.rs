@@ -3,7 +3,7 @@
 #![cfg_attr(feature = "no_unsafe", forbid(unsafe_code))]
 #![warn(missing_docs)]
 #![warn(rust_2018_idioms)]
-#![doc(html_root_url = "https://docs.rs/cidr/0.3.1")]
+#![doc(html_root_url = "https://docs.rs/cidr/0.4.0")]
 #![allow(clippy::match_like_matches_macro)]
 
 //! This library provides types to represent an IP network ([`Cidr`]) or
@@ -18,6 +18,19 @@
 //!
 //! Custom parsing can be implemented using the helpers in the [`parsers`] module.
 //!
+//! Prefixes can be encoded and decoded in the BGP NLRI format (RFC 4271)
+//! with [`Ipv4Cidr::write_nlri`] and [`Ipv4Cidr::parse_nlri`] (and the
+//! [`Ipv6Cidr`] equivalents).
+//!
+//! All types have a fixed-size byte representation (`to_bytes` and
+//! `from_bytes`, see [`packed`]).
+//!
+//! All network and host types can also be parsed in const context with
+//! `parse_const` (e.g. [`Ipv4Cidr::parse_const`]).  Constant values can be
+//! created from string literals with the macros [`cidr!`], [`ipv4_cidr!`],
+//! [`ipv6_cidr!`], [`inet!`], [`ipv4_inet!`] and [`ipv6_inet!`]; the input
+//! is parsed at compile time, invalid input fails the build.
+//!
 //! If the `#` flag is used with the `Display` formatting (i.e. `{:#}`) the
 //! prefix length will be shown even for host addresses (added in `0.1.1`).
 //!
@@ -28,7 +41,13 @@
 //!
 //! # Feature `std`
 //!
-//! Enabled by default, currently unused.
+//! Enabled by default, enables `alloc`.
+//!
+//! # Feature `alloc`
+//!
+//! Enables the `ipam` module with allocators handing out subnets of
+//! address pools, the `prefix_filter` module with router-like prefix
+//! lists and the `rpki` module for route origin validation.
 //!
 //! # Feature `serde`
 //!
@@ -48,6 +67,15 @@
 //!
 //! The represenation hasn't been changed in 0.2; it is compatible with 0.1.
 //!
+//! The error types in [`errors`] can be serialized (but not deserialized)
+//! as a struct with the fields `kind` ([`errors::ErrorKind`]) and
+//! `message` (the `Display` output).
+//!
+//! # Feature `serde_json`
+//!
+//! Enables loading ROAs from JSON exports in the `rpki` module (enables
+//! `alloc`).
+//!
 //! # Feature `bitstring`
 //!
 //! This feature allows various types to be used as [`bitstring::BitString`],
@@ -55,6 +83,56 @@
 //!
 //! [bitstring-trees]: https://crates.io/crates/bitstring-trees
 //!
+//! # Features `ipnet` and `ipnetwork`
+//!
+//! These features implement conversions between the types of this crate
+//! and the network types of the [ipnet] and [ipnetwork] crates.
+//!
+//! Neither of those crates requires the host part of a network to be
+//! zero, so they map naturally to the [`Inet`] types (`From` in both
+//! directions).  [`Cidr`] types can always be converted into them, but
+//! the reverse conversion (`TryFrom`) fails with
+//! [`NetworkParseError::InvalidHostPart`] if the host part isn't zero;
+//! convert to the [`Inet`] type and use its `network()` method to
+//! explicitly truncate the host part instead.
+//!
+//! # Features `bytemuck` and `zerocopy`
+//!
+//! These features make the `Packed*` types in the [`packed`] module
+//! usable with the [bytemuck] and [zerocopy] crates, e.g. to access
+//! large lists of networks in memory-mapped files without copying.
+//!
+//! [bytemuck]: https://crates.io/crates/bytemuck
+//! [zerocopy]: https://crates.io/crates/zerocopy
+//!
+//! # Feature `rand`
+//!
+//! Network ([`Cidr`]) and range ([`InetPair`]) types implement
+//! `Distribution` of the [rand] crate for their address and host types,
+//! sampling uniformly from the covered addresses (e.g. to generate
+//! temporary addresses in a /64 as in RFC 4941, or test fixtures).
+//! Random subnets of a given length can be picked with `random_subnet`
+//! on the [`Cidr`] types.
+//!
+//! [rand]: https://crates.io/crates/rand
+//!
+//! # Features `arbitrary` and `proptest`
+//!
+//! All network, host and pair types implement `Arbitrary` of the
+//! [arbitrary] crate (e.g. for fuzzing with cargo-fuzz) and of the
+//! [proptest] crate.  The `strategies` module (requires the `proptest`
+//! feature) provides constrained generators, e.g. networks with a
+//! network length in a given range or hosts within a given network.
+//! Generated values are always valid (e.g. a zero host part in networks).
+//! The `proptest` feature enables `std`, as the proptest crate requires it.
+//!
+//! [arbitrary]: https://crates.io/crates/arbitrary
+//! [proptest]: https://crates.io/crates/proptest
+//!
+//! [ipnet]: https://crates.io/crates/ipnet
+//! [ipnetwork]: https://crates.io/crates/ipnetwork
+//! [`NetworkParseError::InvalidHostPart`]: errors::NetworkParseError::InvalidHostPart
+//!
 //! [`Ipv4Addr`]: core::net::Ipv4Addr
 //! [`Ipv6Addr`]: core::net::Ipv6Addr
 
@@ -64,44 +142,77 @@ pub use self::{
 		IpCidr,
 		Ipv4Cidr,
 		Ipv6Cidr,
+		NlriIterator,
 	},
 	family::Family,
 	inet::{
+		AnyIpInet,
 		IpInet,
 		Ipv4Inet,
 		Ipv6Inet,
 	},
 	inet_iterator::{
+		AnyIpCidrIterator,
 		InetAddressIterator,
 		InetIterator,
+		InetStrideIterator,
 	},
 	inet_pair::{
 		IpInetPair,
 		Ipv4InetPair,
 		Ipv6InetPair,
 	},
+	num::NumberOfAddresses,
+	permuted_iterator::{
+		InetPermutedAddressIterator,
+		InetPermutedIterator,
+	},
 	traits::{
 		Address,
 		Cidr,
 		Inet,
 		InetPair,
+		NetworkLike,
 	},
 };
 
+#[doc(hidden)]
+pub use self::const_parser::__private;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[macro_use]
 mod display_buffer;
+mod macros;
 
+pub mod anonymize;
+pub mod bucket;
 pub mod errors;
+#[cfg(feature = "alloc")]
+pub mod ipam;
+pub mod packed;
 pub mod parsers;
+#[cfg(feature = "alloc")]
+pub mod prefix_filter;
+#[cfg(feature = "alloc")]
+pub mod rpki;
+#[cfg(feature = "proptest")]
+pub mod strategies;
 
 mod serde_common;
 
 mod address;
+mod arbitrary_impls;
 mod cidr;
+mod const_parser;
 mod family;
 mod inet;
 mod inet_iterator;
 mod inet_pair;
 mod internal_traits;
+mod net_compat;
 mod num;
+mod permuted_iterator;
+mod rand_impls;
 mod traits;
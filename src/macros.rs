@@ -0,0 +1,137 @@
+// literal macros: parse networks and hosts at compile time
+
+/// Create an [`Ipv4Cidr`](crate::Ipv4Cidr) from a string literal at
+/// compile time
+///
+/// Accepts the same input as the `FromStr` implementation; invalid input
+/// (including a non-zero host part) fails the build.  The result can be
+/// used in `const` items.
+///
+/// ```
+/// # use cidr::{ipv4_cidr, Ipv4Cidr};
+/// const PRIVATE: [Ipv4Cidr; 3] = [
+///     ipv4_cidr!("10.0.0.0/8"),
+///     ipv4_cidr!("172.16.0.0/12"),
+///     ipv4_cidr!("192.168.0.0/16"),
+/// ];
+/// assert_eq!(PRIVATE[0], "10.0.0.0/8".parse().unwrap());
+/// ```
+///
+/// ```compile_fail
+/// # use cidr::ipv4_cidr;
+/// let _ = ipv4_cidr!("10.0.0.1/8");
+/// ```
+#[macro_export]
+macro_rules! ipv4_cidr {
+	($s:expr) => {{
+		const VALUE: $crate::Ipv4Cidr = $crate::__private::ipv4_cidr($s);
+		VALUE
+	}};
+}
+
+/// Create an [`Ipv6Cidr`](crate::Ipv6Cidr) from a string literal at
+/// compile time
+///
+/// See [`ipv4_cidr!`] for details.
+///
+/// ```
+/// # use cidr::{ipv6_cidr, Ipv6Cidr};
+/// const DOCUMENTATION: Ipv6Cidr = ipv6_cidr!("2001:db8::/32");
+/// assert_eq!(DOCUMENTATION.network_length(), 32);
+/// ```
+///
+/// ```compile_fail
+/// # use cidr::ipv6_cidr;
+/// let _ = ipv6_cidr!("2001:db8::/129");
+/// ```
+#[macro_export]
+macro_rules! ipv6_cidr {
+	($s:expr) => {{
+		const VALUE: $crate::Ipv6Cidr = $crate::__private::ipv6_cidr($s);
+		VALUE
+	}};
+}
+
+/// Create an [`IpCidr`](crate::IpCidr) from a string literal at compile
+/// time
+///
+/// See [`ipv4_cidr!`] for details.
+///
+/// ```
+/// # use cidr::{cidr, IpCidr};
+/// const NETWORKS: [IpCidr; 2] = [cidr!("10.0.0.0/8"), cidr!("2001:db8::/32")];
+/// assert!(NETWORKS[0].is_ipv4());
+/// assert!(NETWORKS[1].is_ipv6());
+/// ```
+///
+/// ```compile_fail
+/// # use cidr::cidr;
+/// let _ = cidr!("10.0.0.0/33");
+/// ```
+#[macro_export]
+macro_rules! cidr {
+	($s:expr) => {{
+		const VALUE: $crate::IpCidr = $crate::__private::ip_cidr($s);
+		VALUE
+	}};
+}
+
+/// Create an [`Ipv4Inet`](crate::Ipv4Inet) from a string literal at
+/// compile time
+///
+/// Accepts the same input as the `FromStr` implementation; invalid input
+/// fails the build.  The result can be used in `const` items.
+///
+/// ```
+/// # use cidr::{ipv4_inet, Ipv4Inet};
+/// const GATEWAY: Ipv4Inet = ipv4_inet!("192.0.2.1/24");
+/// assert_eq!(GATEWAY.network(), "192.0.2.0/24".parse().unwrap());
+/// ```
+///
+/// ```compile_fail
+/// # use cidr::ipv4_inet;
+/// let _ = ipv4_inet!("192.0.2.256/24");
+/// ```
+#[macro_export]
+macro_rules! ipv4_inet {
+	($s:expr) => {{
+		const VALUE: $crate::Ipv4Inet = $crate::__private::ipv4_inet($s);
+		VALUE
+	}};
+}
+
+/// Create an [`Ipv6Inet`](crate::Ipv6Inet) from a string literal at
+/// compile time
+///
+/// See [`ipv4_inet!`] for details.
+///
+/// ```
+/// # use cidr::{ipv6_inet, Ipv6Inet};
+/// const GATEWAY: Ipv6Inet = ipv6_inet!("2001:db8::1/64");
+/// assert_eq!(GATEWAY.network_length(), 64);
+/// ```
+#[macro_export]
+macro_rules! ipv6_inet {
+	($s:expr) => {{
+		const VALUE: $crate::Ipv6Inet = $crate::__private::ipv6_inet($s);
+		VALUE
+	}};
+}
+
+/// Create an [`IpInet`](crate::IpInet) from a string literal at compile
+/// time
+///
+/// See [`ipv4_inet!`] for details.
+///
+/// ```
+/// # use cidr::{inet, IpInet};
+/// const GATEWAY: IpInet = inet!("192.0.2.1/24");
+/// assert!(GATEWAY.is_ipv4());
+/// ```
+#[macro_export]
+macro_rules! inet {
+	($s:expr) => {{
+		const VALUE: $crate::IpInet = $crate::__private::ip_inet($s);
+		VALUE
+	}};
+}
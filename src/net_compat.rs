@@ -0,0 +1,205 @@
+#![cfg(any(feature = "ipnet", feature = "ipnetwork"))]
+
+// conversions between the `ipnet` / `ipnetwork` types and our types
+//
+// Neither crate requires the host part of a network to be zero (like our
+// `Inet` types); converting their networks to `Cidr` types fails if the
+// host part is not zero.  Use `Inet::network` after converting to an
+// `Inet` type to explicitly truncate the host part instead.
+//
+// Both crates have the same shape (one type per family and an enum over
+// both), only the names differ.
+
+macro_rules! impl_net_family_for {
+	($krate:ident : $net:ident : cidr $cidr:ident : inet $inet:ident : addr $addr:ident : prefix $prefix:ident) => {
+		impl From<$inet> for $net {
+			fn from(inet: $inet) -> Self {
+				$net::new(inet.address, inet.network_length).expect("valid network length")
+			}
+		}
+
+		#[doc = concat!("Keeps the host part of the `", stringify!($krate), "` network.")]
+		impl From<$net> for $inet {
+			fn from(net: $net) -> Self {
+				$inet::new(net.$addr(), net.$prefix()).expect("valid network length")
+			}
+		}
+
+		impl From<$cidr> for $net {
+			fn from(cidr: $cidr) -> Self {
+				$net::new(cidr.address, cidr.network_length).expect("valid network length")
+			}
+		}
+
+		#[doc = concat!(
+					"Fails with [`NetworkParseError::InvalidHostPart`] if the host\n",
+					"part of the `", stringify!($krate), "` network is not zero; convert to the [`Inet`]\n",
+					"type and use its `network()` method to truncate instead.\n",
+					"\n",
+					"[`Inet`]: crate::Inet",
+				)]
+		impl TryFrom<$net> for $cidr {
+			type Error = NetworkParseError;
+
+			fn try_from(net: $net) -> Result<Self, NetworkParseError> {
+				$cidr::new(net.$addr(), net.$prefix())
+			}
+		}
+	};
+}
+
+macro_rules! impl_net_compat_for {
+	($krate:ident : $ipnet:ident, $ipv4net:ident, $ipv6net:ident : addr $addr:ident : prefix $prefix:ident) => {
+		use core::net::IpAddr;
+
+		use $krate::{
+			$ipnet,
+			$ipv4net,
+			$ipv6net,
+		};
+
+		use crate::{
+			errors::NetworkParseError,
+			IpCidr,
+			IpInet,
+			Ipv4Cidr,
+			Ipv4Inet,
+			Ipv6Cidr,
+			Ipv6Inet,
+		};
+
+		impl_net_family_for! {$krate : $ipv4net : cidr Ipv4Cidr : inet Ipv4Inet : addr $addr : prefix $prefix}
+		impl_net_family_for! {$krate : $ipv6net : cidr Ipv6Cidr : inet Ipv6Inet : addr $addr : prefix $prefix}
+
+		impl From<IpInet> for $ipnet {
+			fn from(inet: IpInet) -> Self {
+				match inet {
+					IpInet::V4(inet) => $ipnet::V4(inet.into()),
+					IpInet::V6(inet) => $ipnet::V6(inet.into()),
+				}
+			}
+		}
+
+		#[doc = concat!("Keeps the host part of the `", stringify!($krate), "` network.")]
+		impl From<$ipnet> for IpInet {
+			fn from(net: $ipnet) -> Self {
+				match net {
+					$ipnet::V4(net) => IpInet::V4(net.into()),
+					$ipnet::V6(net) => IpInet::V6(net.into()),
+				}
+			}
+		}
+
+		impl From<IpCidr> for $ipnet {
+			fn from(cidr: IpCidr) -> Self {
+				match cidr {
+					IpCidr::V4(cidr) => $ipnet::V4(cidr.into()),
+					IpCidr::V6(cidr) => $ipnet::V6(cidr.into()),
+				}
+			}
+		}
+
+		#[doc = concat!(
+			"Fails with [`NetworkParseError::InvalidHostPart`] if the host part of\n",
+			"the `", stringify!($krate), "` network is not zero; convert to [`IpInet`] and use\n",
+			"[`IpInet::network`] to truncate instead.",
+		)]
+		impl TryFrom<$ipnet> for IpCidr {
+			type Error = NetworkParseError;
+
+			fn try_from(net: $ipnet) -> Result<Self, NetworkParseError> {
+				let addr: IpAddr = net.$addr();
+				IpCidr::new(addr, net.$prefix())
+			}
+		}
+
+		#[cfg(test)]
+		mod tests {
+			use $krate::{
+				$ipnet,
+				$ipv4net,
+				$ipv6net,
+			};
+
+			use crate::{
+				errors::NetworkParseError,
+				IpCidr,
+				IpInet,
+				Ipv4Cidr,
+				Ipv4Inet,
+				Ipv6Cidr,
+				Ipv6Inet,
+			};
+
+			#[test]
+			fn v4_inet_roundtrip() {
+				let inet: Ipv4Inet = "192.0.2.1/24".parse().unwrap();
+				let net: $ipv4net = inet.into();
+				assert_eq!(net, "192.0.2.1/24".parse::<$ipv4net>().unwrap());
+				assert_eq!(Ipv4Inet::from(net), inet);
+			}
+
+			#[test]
+			fn v4_cidr() {
+				let cidr: Ipv4Cidr = "192.0.2.0/24".parse().unwrap();
+				let net: $ipv4net = cidr.into();
+				assert_eq!(net, "192.0.2.0/24".parse::<$ipv4net>().unwrap());
+				assert_eq!(Ipv4Cidr::try_from(net), Ok(cidr));
+
+				let net: $ipv4net = "192.0.2.1/24".parse().unwrap();
+				assert!(matches!(
+					Ipv4Cidr::try_from(net),
+					Err(NetworkParseError::InvalidHostPart(_))
+				));
+				assert_eq!(Ipv4Inet::from(net).network(), cidr);
+			}
+
+			#[test]
+			fn v6_inet_roundtrip() {
+				let inet: Ipv6Inet = "2001:db8::1/64".parse().unwrap();
+				let net: $ipv6net = inet.into();
+				assert_eq!(net, "2001:db8::1/64".parse::<$ipv6net>().unwrap());
+				assert_eq!(Ipv6Inet::from(net), inet);
+			}
+
+			#[test]
+			fn v6_cidr() {
+				let cidr: Ipv6Cidr = "2001:db8::/64".parse().unwrap();
+				let net: $ipv6net = cidr.into();
+				assert_eq!(Ipv6Cidr::try_from(net), Ok(cidr));
+
+				let net: $ipv6net = "2001:db8::1/64".parse().unwrap();
+				assert!(matches!(
+					Ipv6Cidr::try_from(net),
+					Err(NetworkParseError::InvalidHostPart(_))
+				));
+			}
+
+			#[test]
+			fn ip() {
+				let inet: IpInet = "2001:db8::1/64".parse().unwrap();
+				let net: $ipnet = inet.into();
+				assert_eq!(IpInet::from(net), inet);
+				assert!(matches!(
+					IpCidr::try_from(net),
+					Err(NetworkParseError::InvalidHostPart(_))
+				));
+
+				let cidr: IpCidr = "10.0.0.0/8".parse().unwrap();
+				let net: $ipnet = cidr.into();
+				assert_eq!(net, "10.0.0.0/8".parse::<$ipnet>().unwrap());
+				assert_eq!(IpCidr::try_from(net), Ok(cidr));
+			}
+		}
+	};
+}
+
+#[cfg(feature = "ipnet")]
+mod ipnet_compat {
+	impl_net_compat_for! {ipnet : IpNet, Ipv4Net, Ipv6Net : addr addr : prefix prefix_len}
+}
+
+#[cfg(feature = "ipnetwork")]
+mod ipnetwork_compat {
+	impl_net_compat_for! {ipnetwork : IpNetwork, Ipv4Network, Ipv6Network : addr ip : prefix prefix}
+}
@@ -0,0 +1,499 @@
+//! Fixed-size byte representations
+//!
+//! All [`Cidr`] and [`Inet`] types (and [`AnyIpCidr`] and [`AnyIpInet`])
+//! can be converted to
+//! and from fixed-size byte arrays with `to_bytes` and `from_bytes`.
+//!
+//! The first byte is a tag, using the same values as the binary `serde`
+//! format:
+//!
+//! - `0x00...0x20`: IPv4 with network length `tag`
+//! - `0x40...0xc0`: IPv6 with network length `tag - 0x40`
+//! - `0xff`: `any`
+//!
+//! The tag is followed by the address octets in network byte order: IPv4
+//! types use 5 bytes, IPv6 types 17 bytes.  Types that can represent
+//! either family also use 17 bytes; IPv4 addresses (and `any`) are padded
+//! with zero bytes.  As the tag also identifies the family, the
+//! representation of an IPv6 value is the same for [`Ipv6Cidr`],
+//! [`IpCidr`] and [`AnyIpCidr`] (and an IPv4 value only differs in the
+//! padding).
+//!
+//! The `Packed*` types in this module wrap such a byte array (alignment
+//! 1, no padding) to store large amounts of values, e.g. in
+//! memory-mapped files.  They can hold arbitrary bytes, so reading the
+//! value back ([`PackedIpv4Cidr::get`] and similar) can fail.
+//!
+//! With the `bytemuck` feature the `Packed*` types implement
+//! `bytemuck::Zeroable` and `bytemuck::Pod`; with the `zerocopy` feature
+//! they implement `zerocopy::FromBytes`, `zerocopy::IntoBytes`,
+//! `zerocopy::Immutable`, `zerocopy::KnownLayout` and
+//! `zerocopy::Unaligned`.
+//!
+//! [`Cidr`]: crate::Cidr
+//! [`Inet`]: crate::Inet
+
+use core::net::{
+	IpAddr,
+	Ipv4Addr,
+	Ipv6Addr,
+};
+
+use crate::{
+	errors::FromBytesError,
+	internal_traits::PrivUnspecAddress,
+	AnyIpCidr,
+	AnyIpInet,
+	IpCidr,
+	IpInet,
+	Ipv4Cidr,
+	Ipv4Inet,
+	Ipv6Cidr,
+	Ipv6Inet,
+};
+
+const TAG_IPV6: u8 = 0x40;
+const TAG_ANY: u8 = 0xff;
+
+const fn encode_v4(address: Ipv4Addr, len: u8) -> [u8; 5] {
+	let o = address.octets();
+	[len, o[0], o[1], o[2], o[3]]
+}
+
+const fn encode_v6(address: Ipv6Addr, len: u8) -> [u8; 17] {
+	let o = address.octets();
+	let mut result = [0u8; 17];
+	result[0] = TAG_IPV6 + len;
+	let mut i = 0;
+	while i < 16 {
+		result[1 + i] = o[i];
+		i += 1;
+	}
+	result
+}
+
+const fn encode_ip(data: Option<(IpAddr, u8)>) -> [u8; 17] {
+	match data {
+		None => {
+			let mut result = [0u8; 17];
+			result[0] = TAG_ANY;
+			result
+		},
+		Some((IpAddr::V4(address), len)) => {
+			let v4 = encode_v4(address, len);
+			let mut result = [0u8; 17];
+			let mut i = 0;
+			while i < 5 {
+				result[i] = v4[i];
+				i += 1;
+			}
+			result
+		},
+		Some((IpAddr::V6(address), len)) => encode_v6(address, len),
+	}
+}
+
+// only looks at the first 5 bytes
+const fn decode_v4(bytes: &[u8]) -> Result<(Ipv4Addr, u8), FromBytesError> {
+	let tag = bytes[0];
+	if tag > 32 {
+		return Err(FromBytesError::InvalidTag(tag));
+	}
+	Ok((Ipv4Addr::new(bytes[1], bytes[2], bytes[3], bytes[4]), tag))
+}
+
+const fn decode_v6(bytes: &[u8; 17]) -> Result<(Ipv6Addr, u8), FromBytesError> {
+	let tag = bytes[0];
+	if tag < TAG_IPV6 || tag > TAG_IPV6 + 128 {
+		return Err(FromBytesError::InvalidTag(tag));
+	}
+	let mut octets = [0u8; 16];
+	let mut i = 0;
+	while i < 16 {
+		octets[i] = bytes[1 + i];
+		i += 1;
+	}
+	let address = <Ipv6Addr as PrivUnspecAddress>::_Tools::from_native(u128::from_be_bytes(octets));
+	Ok((address, tag - TAG_IPV6))
+}
+
+const fn check_padding(bytes: &[u8; 17], from: usize) -> Result<(), FromBytesError> {
+	let mut i = from;
+	while i < 17 {
+		if bytes[i] != 0 {
+			return Err(FromBytesError::NonZeroPadding);
+		}
+		i += 1;
+	}
+	Ok(())
+}
+
+const fn decode_ip(bytes: &[u8; 17]) -> Result<Option<(IpAddr, u8)>, FromBytesError> {
+	let tag = bytes[0];
+	if tag == TAG_ANY {
+		return match check_padding(bytes, 1) {
+			Ok(()) => Ok(None),
+			Err(e) => Err(e),
+		};
+	}
+	if tag < TAG_IPV6 {
+		if let Err(e) = check_padding(bytes, 5) {
+			return Err(e);
+		}
+		match decode_v4(bytes) {
+			Ok((address, len)) => Ok(Some((IpAddr::V4(address), len))),
+			Err(e) => Err(e),
+		}
+	} else {
+		match decode_v6(bytes) {
+			Ok((address, len)) => Ok(Some((IpAddr::V6(address), len))),
+			Err(e) => Err(e),
+		}
+	}
+}
+
+macro_rules! impl_bytes_for_cidr {
+	($n:ident : addr $addr:ident : size $size:literal : encode $encode:ident : decode $decode:ident) => {
+		impl $n {
+			#[doc = concat!("Fixed-size (", $size, " bytes) representation; see [`packed`](crate::packed) for the format")]
+			pub const fn to_bytes(&self) -> [u8; $size] {
+				$encode(self.address, self.network_length)
+			}
+
+			/// Decode fixed-size representation created by [`to_bytes`]
+			///
+			/// [`to_bytes`]: Self::to_bytes
+			pub const fn from_bytes(bytes: [u8; $size]) -> Result<Self, FromBytesError> {
+				match $decode(&bytes) {
+					Ok((address, network_length)) => {
+						if !<$addr as PrivUnspecAddress>::_Tools::_has_zero_host_part(
+							address,
+							network_length,
+						) {
+							return Err(FromBytesError::InvalidHostPart);
+						}
+						Ok(Self {
+							address,
+							network_length,
+						})
+					},
+					Err(e) => Err(e),
+				}
+			}
+		}
+	};
+}
+
+impl_bytes_for_cidr! {Ipv4Cidr : addr Ipv4Addr : size 5 : encode encode_v4 : decode decode_v4}
+impl_bytes_for_cidr! {Ipv6Cidr : addr Ipv6Addr : size 17 : encode encode_v6 : decode decode_v6}
+
+macro_rules! impl_bytes_for_inet {
+	($n:ident : size $size:literal : encode $encode:ident : decode $decode:ident) => {
+		impl $n {
+			#[doc = concat!("Fixed-size (", $size, " bytes) representation; see [`packed`](crate::packed) for the format")]
+			pub const fn to_bytes(&self) -> [u8; $size] {
+				$encode(self.address, self.network_length)
+			}
+
+			/// Decode fixed-size representation created by [`to_bytes`]
+			///
+			/// [`to_bytes`]: Self::to_bytes
+			pub const fn from_bytes(bytes: [u8; $size]) -> Result<Self, FromBytesError> {
+				match $decode(&bytes) {
+					Ok((address, network_length)) => Ok(Self {
+						address,
+						network_length,
+					}),
+					Err(e) => Err(e),
+				}
+			}
+		}
+	};
+}
+
+impl_bytes_for_inet! {Ipv4Inet : size 5 : encode encode_v4 : decode decode_v4}
+impl_bytes_for_inet! {Ipv6Inet : size 17 : encode encode_v6 : decode decode_v6}
+
+impl IpCidr {
+	/// Fixed-size (17 bytes) representation; see [`packed`](crate::packed)
+	/// for the format
+	pub const fn to_bytes(&self) -> [u8; 17] {
+		match self {
+			Self::V4(c) => encode_ip(Some((IpAddr::V4(c.address), c.network_length))),
+			Self::V6(c) => c.to_bytes(),
+		}
+	}
+
+	/// Decode fixed-size representation created by [`to_bytes`]
+	///
+	/// [`to_bytes`]: Self::to_bytes
+	pub const fn from_bytes(bytes: [u8; 17]) -> Result<Self, FromBytesError> {
+		match AnyIpCidr::from_bytes(bytes) {
+			Ok(AnyIpCidr::Any) => Err(FromBytesError::InvalidTag(TAG_ANY)),
+			Ok(AnyIpCidr::V4(c)) => Ok(Self::V4(c)),
+			Ok(AnyIpCidr::V6(c)) => Ok(Self::V6(c)),
+			Err(e) => Err(e),
+		}
+	}
+}
+
+impl AnyIpCidr {
+	/// Fixed-size (17 bytes) representation; see [`packed`](crate::packed)
+	/// for the format
+	pub const fn to_bytes(&self) -> [u8; 17] {
+		match self {
+			Self::Any => encode_ip(None),
+			Self::V4(c) => encode_ip(Some((IpAddr::V4(c.address), c.network_length))),
+			Self::V6(c) => c.to_bytes(),
+		}
+	}
+
+	/// Decode fixed-size representation created by [`to_bytes`]
+	///
+	/// [`to_bytes`]: Self::to_bytes
+	pub const fn from_bytes(bytes: [u8; 17]) -> Result<Self, FromBytesError> {
+		match decode_ip(&bytes) {
+			Ok(None) => Ok(Self::Any),
+			Ok(Some((address, len))) => match Self::new(address, len) {
+				Ok(c) => Ok(c),
+				// length was already validated through the tag
+				Err(_) => Err(FromBytesError::InvalidHostPart),
+			},
+			Err(e) => Err(e),
+		}
+	}
+}
+
+impl IpInet {
+	/// Fixed-size (17 bytes) representation; see [`packed`](crate::packed)
+	/// for the format
+	pub const fn to_bytes(&self) -> [u8; 17] {
+		match self {
+			Self::V4(c) => encode_ip(Some((IpAddr::V4(c.address), c.network_length))),
+			Self::V6(c) => c.to_bytes(),
+		}
+	}
+
+	/// Decode fixed-size representation created by [`to_bytes`]
+	///
+	/// [`to_bytes`]: Self::to_bytes
+	pub const fn from_bytes(bytes: [u8; 17]) -> Result<Self, FromBytesError> {
+		match decode_ip(&bytes) {
+			Ok(None) => Err(FromBytesError::InvalidTag(TAG_ANY)),
+			Ok(Some((IpAddr::V4(address), network_length))) => Ok(Self::V4(Ipv4Inet {
+				address,
+				network_length,
+			})),
+			Ok(Some((IpAddr::V6(address), network_length))) => Ok(Self::V6(Ipv6Inet {
+				address,
+				network_length,
+			})),
+			Err(e) => Err(e),
+		}
+	}
+}
+
+impl AnyIpInet {
+	/// Fixed-size (17 bytes) representation; see [`packed`](crate::packed)
+	/// for the format
+	pub const fn to_bytes(&self) -> [u8; 17] {
+		match self {
+			Self::Any => encode_ip(None),
+			Self::V4(c) => encode_ip(Some((IpAddr::V4(c.address), c.network_length))),
+			Self::V6(c) => c.to_bytes(),
+		}
+	}
+
+	/// Decode fixed-size representation created by [`to_bytes`]
+	///
+	/// [`to_bytes`]: Self::to_bytes
+	pub const fn from_bytes(bytes: [u8; 17]) -> Result<Self, FromBytesError> {
+		match decode_ip(&bytes) {
+			Ok(None) => Ok(Self::Any),
+			Ok(Some((IpAddr::V4(address), network_length))) => Ok(Self::V4(Ipv4Inet {
+				address,
+				network_length,
+			})),
+			Ok(Some((IpAddr::V6(address), network_length))) => Ok(Self::V6(Ipv6Inet {
+				address,
+				network_length,
+			})),
+			Err(e) => Err(e),
+		}
+	}
+}
+
+macro_rules! packed_type {
+	($packed:ident : $n:ident : size $size:literal) => {
+		#[doc = concat!("Packed [`", stringify!($n), "`](crate::", stringify!($n), ") representation (", $size, " bytes, alignment 1)")]
+		///
+		/// See [module documentation](self) for the format.
+		#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+		#[cfg_attr(feature = "bytemuck", derive(bytemuck::Zeroable, bytemuck::Pod))]
+		#[cfg_attr(
+			feature = "zerocopy",
+			derive(
+				zerocopy::FromBytes,
+				zerocopy::IntoBytes,
+				zerocopy::Immutable,
+				zerocopy::KnownLayout,
+				zerocopy::Unaligned
+			)
+		)]
+		#[repr(transparent)]
+		pub struct $packed(pub [u8; $size]);
+
+		impl $packed {
+			/// Pack value
+			pub const fn new(value: $n) -> Self {
+				Self(value.to_bytes())
+			}
+
+			/// Unpack value; fails if the stored bytes are not a valid
+			/// representation
+			pub const fn get(&self) -> Result<$n, FromBytesError> {
+				$n::from_bytes(self.0)
+			}
+		}
+
+		impl From<$n> for $packed {
+			fn from(value: $n) -> Self {
+				Self::new(value)
+			}
+		}
+
+		impl TryFrom<$packed> for $n {
+			type Error = FromBytesError;
+
+			fn try_from(packed: $packed) -> Result<Self, FromBytesError> {
+				packed.get()
+			}
+		}
+	};
+}
+
+packed_type! {PackedIpv4Cidr : Ipv4Cidr : size 5}
+packed_type! {PackedIpv6Cidr : Ipv6Cidr : size 17}
+packed_type! {PackedIpCidr : IpCidr : size 17}
+packed_type! {PackedAnyIpCidr : AnyIpCidr : size 17}
+packed_type! {PackedIpv4Inet : Ipv4Inet : size 5}
+packed_type! {PackedIpv6Inet : Ipv6Inet : size 17}
+packed_type! {PackedIpInet : IpInet : size 17}
+packed_type! {PackedAnyIpInet : AnyIpInet : size 17}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn v4() {
+		let cidr: Ipv4Cidr = "192.0.2.0/24".parse().unwrap();
+		assert_eq!(cidr.to_bytes(), [24, 192, 0, 2, 0]);
+		assert_eq!(Ipv4Cidr::from_bytes(cidr.to_bytes()), Ok(cidr));
+		assert_eq!(
+			Ipv4Cidr::from_bytes([24, 192, 0, 2, 1]),
+			Err(FromBytesError::InvalidHostPart)
+		);
+		assert_eq!(
+			Ipv4Cidr::from_bytes([33, 192, 0, 2, 0]),
+			Err(FromBytesError::InvalidTag(33))
+		);
+
+		let inet: Ipv4Inet = "192.0.2.1/24".parse().unwrap();
+		assert_eq!(inet.to_bytes(), [24, 192, 0, 2, 1]);
+		assert_eq!(Ipv4Inet::from_bytes(inet.to_bytes()), Ok(inet));
+	}
+
+	#[test]
+	fn v6() {
+		let cidr: Ipv6Cidr = "2001:db8::/32".parse().unwrap();
+		let bytes = cidr.to_bytes();
+		assert_eq!(
+			bytes,
+			[0x60, 0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+		);
+		assert_eq!(Ipv6Cidr::from_bytes(bytes), Ok(cidr));
+		assert_eq!(IpCidr::from_bytes(bytes), Ok(IpCidr::V6(cidr)));
+		assert_eq!(AnyIpCidr::from_bytes(bytes), Ok(AnyIpCidr::V6(cidr)));
+		assert_eq!(IpCidr::V6(cidr).to_bytes(), bytes);
+
+		let inet: Ipv6Inet = "2001:db8::1/32".parse().unwrap();
+		assert_eq!(Ipv6Inet::from_bytes(inet.to_bytes()), Ok(inet));
+		assert_eq!(
+			Ipv6Cidr::from_bytes(inet.to_bytes()),
+			Err(FromBytesError::InvalidHostPart)
+		);
+		assert_eq!(
+			Ipv6Inet::from_bytes([24; 17]),
+			Err(FromBytesError::InvalidTag(24))
+		);
+	}
+
+	#[test]
+	fn ip() {
+		let cidr: IpCidr = "10.0.0.0/8".parse().unwrap();
+		let bytes = cidr.to_bytes();
+		assert_eq!(bytes, [8, 10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+		assert_eq!(IpCidr::from_bytes(bytes), Ok(cidr));
+		let mut invalid = bytes;
+		invalid[16] = 1;
+		assert_eq!(
+			IpCidr::from_bytes(invalid),
+			Err(FromBytesError::NonZeroPadding)
+		);
+
+		let inet: IpInet = "10.0.0.1/8".parse().unwrap();
+		assert_eq!(IpInet::from_bytes(inet.to_bytes()), Ok(inet));
+
+		let any = AnyIpCidr::Any.to_bytes();
+		assert_eq!(any[0], 0xff);
+		assert_eq!(AnyIpCidr::from_bytes(any), Ok(AnyIpCidr::Any));
+		assert_eq!(
+			IpCidr::from_bytes(any),
+			Err(FromBytesError::InvalidTag(0xff))
+		);
+		assert_eq!(
+			IpInet::from_bytes(any),
+			Err(FromBytesError::InvalidTag(0xff))
+		);
+		assert_eq!(AnyIpInet::from_bytes(any), Ok(AnyIpInet::Any));
+		assert_eq!(AnyIpInet::Any.to_bytes(), any);
+		assert_eq!(
+			AnyIpInet::from_bytes(inet.to_bytes()),
+			Ok(AnyIpInet::from(inet))
+		);
+	}
+
+	#[test]
+	fn packed() {
+		let cidr: Ipv4Cidr = "192.0.2.0/24".parse().unwrap();
+		let packed = PackedIpv4Cidr::from(cidr);
+		assert_eq!(core::mem::size_of::<PackedIpv4Cidr>(), 5);
+		assert_eq!(core::mem::align_of::<PackedIpCidr>(), 1);
+		assert_eq!(packed.get(), Ok(cidr));
+		assert_eq!(
+			PackedIpv4Cidr([0xff; 5]).get(),
+			Err(FromBytesError::InvalidTag(0xff))
+		);
+	}
+
+	#[cfg(feature = "bytemuck")]
+	#[test]
+	fn packed_bytemuck() {
+		let data = [8u8, 10, 0, 0, 0, 24, 192, 0, 2, 0];
+		let list: &[PackedIpv4Cidr] = bytemuck::cast_slice(&data);
+		assert_eq!(list[0].get(), Ok("10.0.0.0/8".parse().unwrap()));
+		assert_eq!(list[1].get(), Ok("192.0.2.0/24".parse().unwrap()));
+	}
+
+	#[cfg(feature = "zerocopy")]
+	#[test]
+	fn packed_zerocopy() {
+		use zerocopy::FromBytes;
+
+		let data = [8u8, 10, 0, 0, 0, 24, 192, 0, 2, 0];
+		let list = <[PackedIpv4Cidr]>::ref_from_bytes(&data).unwrap();
+		assert_eq!(list[0].get(), Ok("10.0.0.0/8".parse().unwrap()));
+		assert_eq!(list[1].get(), Ok("192.0.2.0/24".parse().unwrap()));
+	}
+}
@@ -14,10 +14,28 @@ use crate::{
 };
 
 fn parse_prefix_len(s: &str) -> Result<u8, NetworkParseError> {
+	if s.is_empty() {
+		return Err(NetworkParseError::EmptyNetworkLength);
+	}
 	s.parse()
 		.map_err(NetworkParseError::NetworkLengthParseError)
 }
 
+// the address is everything before the last '/'; report additional
+// separators instead of the address parse error
+fn parse_address<A, AP>(s: &str, address_parser: AP) -> Result<A, NetworkParseError>
+where
+	AP: FnOnce(&str) -> Result<A, AddrParseError>,
+{
+	address_parser(s).map_err(|e| {
+		if s.contains('/') {
+			NetworkParseError::ExtraSeparator
+		} else {
+			NetworkParseError::AddrParseError(e)
+		}
+	})
+}
+
 /// Parse [`Cidr`] with custom address and network (when no '/' separator was found) parser
 ///
 /// If a '/' is found, parse trailing number as prefix length and leading address with `address_parser`.
@@ -35,7 +53,7 @@ where
 	match s.rfind('/') {
 		None => host_parser(s),
 		Some(pos) => C::new(
-			address_parser(&s[0..pos])?,
+			parse_address(&s[0..pos], address_parser)?,
 			parse_prefix_len(&s[pos + 1..])?,
 		),
 	}
@@ -70,7 +88,7 @@ where
 		None => host_parser(s),
 		Some(pos) => {
 			let inet = <C::Address as Address>::Inet::new(
-				address_parser(&s[0..pos])?,
+				parse_address(&s[0..pos], address_parser)?,
 				parse_prefix_len(&s[pos + 1..])?,
 			)?;
 			Ok(inet.network())
@@ -112,7 +130,7 @@ where
 	match s.rfind('/') {
 		None => Ok(host_parser(s)?.into()),
 		Some(pos) => AnyIpCidr::new(
-			address_parser(&s[0..pos])?,
+			parse_address(&s[0..pos], address_parser)?,
 			parse_prefix_len(&s[pos + 1..])?,
 		),
 	}
@@ -150,7 +168,7 @@ where
 	match s.rfind('/') {
 		None => Ok(host_parser(s)?.into()),
 		Some(pos) => Ok(IpInet::new(
-			address_parser(&s[0..pos])?,
+			parse_address(&s[0..pos], address_parser)?,
 			parse_prefix_len(&s[pos + 1..])?,
 		)?
 		.network()
@@ -190,7 +208,7 @@ where
 	match s.rfind('/') {
 		None => host_parser(s),
 		Some(pos) => Ok(I::new(
-			address_parser(&s[0..pos])?,
+			parse_address(&s[0..pos], address_parser)?,
 			parse_prefix_len(&s[pos + 1..])?,
 		)?),
 	}
@@ -0,0 +1,271 @@
+// iterate over a range of addresses in a pseudo-random order
+//
+// The offsets of the addresses in the range are permuted with a balanced
+// Feistel network over the smallest even number of bits covering the
+// range; results outside the range are fed through the network again
+// ("cycle walking") until they fit.  As the domain of the Feistel network
+// is less than four times the size of the range this needs less than four
+// rounds on average.
+//
+// This isn't meant to be cryptographically secure; it only needs to
+// spread the addresses evenly.
+
+use crate::{
+	internal_traits::PrivInetPair,
+	num::NumberOfAddresses,
+	Address,
+	Inet,
+	InetPair,
+};
+
+const ROUNDS: usize = 4;
+
+// splitmix64 finalizer
+const fn mix(z: u64) -> u64 {
+	let z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+	let z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+	z ^ (z >> 31)
+}
+
+/// Iterator over a list of IP addresses within a network in a
+/// pseudo-random order
+///
+/// Created by [`InetIterator::permuted`] and [`InetIterator::sample`].
+///
+/// Each address is visited exactly once; the order only depends on the
+/// seed and the iterated range, but is not guaranteed to be stable across
+/// releases of this crate.  No memory is needed to track the visited
+/// addresses, so this works for IPv6 networks of any size.
+///
+/// The order is not cryptographically secure; it is meant to spread load
+/// (e.g. when scanning a network), not to hide the order.
+///
+/// [`InetIterator::permuted`]: crate::InetIterator::permuted
+/// [`InetIterator::sample`]: crate::InetIterator::sample
+#[derive(Clone, Copy, Debug)]
+pub struct InetPermutedIterator<A: Address> {
+	// first address in range
+	base: Option<A::Inet>,
+	// offset of the last address in range
+	last_offset: u128,
+	// number of addresses still to yield
+	remaining: NumberOfAddresses,
+	// next index to map through the permutation
+	index: u128,
+	half_bits: u32,
+	keys: [u64; ROUNDS],
+}
+
+impl<A: Address> InetPermutedIterator<A> {
+	pub(crate) fn new(state: Option<A::InetPair>, seed: u64, limit: NumberOfAddresses) -> Self {
+		let (base, last_offset) = match state {
+			None => (None, 0),
+			Some(pair) => {
+				let last_offset = match pair._covered_addresses() {
+					NumberOfAddresses::Count(c) => c - 1,
+					NumberOfAddresses::MaxIpv6Addresses => u128::MAX,
+				};
+				(Some(pair.first()), last_offset)
+			},
+		};
+		let bits = 128 - last_offset.leading_zeros();
+		let half_bits = core::cmp::max(1, bits.div_ceil(2));
+		let mut keys = [0; ROUNDS];
+		let mut key_state = seed;
+		for key in keys.iter_mut() {
+			key_state = key_state.wrapping_add(0x9e3779b97f4a7c15);
+			*key = mix(key_state);
+		}
+		Self {
+			base,
+			last_offset,
+			remaining: if base.is_some() {
+				limit
+			} else {
+				NumberOfAddresses::Count(0)
+			},
+			index: 0,
+			half_bits,
+			keys,
+		}
+	}
+
+	/// Iterate only over addresses (without network prefix length)
+	pub fn addresses(self) -> InetPermutedAddressIterator<A> {
+		InetPermutedAddressIterator { inner: self }
+	}
+
+	/// Exact number of remaining addresses
+	pub fn remaining(&self) -> NumberOfAddresses {
+		self.remaining
+	}
+
+	fn feistel(&self, offset: u128) -> u128 {
+		let mask = (1u128 << self.half_bits) - 1;
+		let mut left = (offset >> self.half_bits) as u64;
+		let mut right = (offset & mask) as u64;
+		for key in self.keys {
+			let next = left ^ (mix(right ^ key) & (mask as u64));
+			left = right;
+			right = next;
+		}
+		((left as u128) << self.half_bits) | right as u128
+	}
+
+	fn permute(&self, index: u128) -> u128 {
+		let mut offset = index;
+		loop {
+			offset = self.feistel(offset);
+			if offset <= self.last_offset {
+				return offset;
+			}
+		}
+	}
+}
+
+impl<A: Address> Iterator for InetPermutedIterator<A> {
+	type Item = A::Inet;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.remaining = match self.remaining {
+			NumberOfAddresses::Count(0) => return None,
+			NumberOfAddresses::Count(c) => NumberOfAddresses::Count(c - 1),
+			NumberOfAddresses::MaxIpv6Addresses => NumberOfAddresses::Count(u128::MAX),
+		};
+		let offset = self.permute(self.index);
+		self.index = self.index.wrapping_add(1);
+		Some(self.base?.overflowing_add(offset).0)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		match self.remaining {
+			NumberOfAddresses::Count(c) if c <= (usize::MAX as u128) => {
+				(c as usize, Some(c as usize))
+			},
+			_ => (usize::MAX, None),
+		}
+	}
+}
+
+impl<A: Address> core::iter::FusedIterator for InetPermutedIterator<A> {}
+
+/// Iterator over a list of IP addresses in a network in a pseudo-random
+/// order
+///
+/// Created by [`InetPermutedIterator::addresses`].
+#[derive(Clone, Copy, Debug)]
+pub struct InetPermutedAddressIterator<A: Address> {
+	inner: InetPermutedIterator<A>,
+}
+
+impl<A: Address> InetPermutedAddressIterator<A> {
+	/// Exact number of remaining addresses
+	pub fn remaining(&self) -> NumberOfAddresses {
+		self.inner.remaining()
+	}
+}
+
+impl<A: Address> Iterator for InetPermutedAddressIterator<A> {
+	type Item = A;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		Some(self.inner.next()?.address())
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.inner.size_hint()
+	}
+}
+
+impl<A: Address> core::iter::FusedIterator for InetPermutedAddressIterator<A> {}
+
+#[cfg(test)]
+mod tests {
+	use core::net::{
+		Ipv4Addr,
+		Ipv6Addr,
+	};
+
+	use crate::{
+		IpCidr,
+		Ipv4Cidr,
+		Ipv6Cidr,
+		NumberOfAddresses,
+	};
+
+	// every offset must be visited exactly once
+	fn check_permutation(len: u8, seed: u64) {
+		let cidr = Ipv4Cidr::new(Ipv4Addr::new(10, 0, 0, 0), len).unwrap();
+		let mut seen = [false; 1024];
+		let mut count = 0;
+		for addr in cidr.iter().permuted(seed).addresses() {
+			let offset = cidr.index_of(&addr).unwrap() as usize;
+			assert!(!seen[offset], "{} visited twice", addr);
+			seen[offset] = true;
+			count += 1;
+		}
+		assert_eq!(count, 1 << (32 - len));
+	}
+
+	#[test]
+	fn permutation() {
+		for len in 22..=32 {
+			for seed in 0..8 {
+				check_permutation(len, seed);
+			}
+		}
+	}
+
+	#[test]
+	fn partial_range() {
+		// iterator already partially consumed: permute the rest
+		let cidr = "192.0.2.0/24".parse::<Ipv4Cidr>().unwrap();
+		let mut iter = cidr.iter();
+		iter.nth(9);
+		iter.nth_back(9);
+		let mut seen = [false; 256];
+		let mut count = 0;
+		for addr in iter.permuted(42).addresses() {
+			let offset = cidr.index_of(&addr).unwrap() as usize;
+			assert!((10..246).contains(&offset), "{} out of range", addr);
+			assert!(!seen[offset]);
+			seen[offset] = true;
+			count += 1;
+		}
+		assert_eq!(count, 236);
+	}
+
+	#[test]
+	fn seed() {
+		let cidr = "2001:db8::/64".parse::<Ipv6Cidr>().unwrap();
+		let a = cidr.iter().permuted(1).addresses();
+		let b = cidr.iter().permuted(1).addresses();
+		let c = cidr.iter().permuted(2).addresses();
+		assert!(a.take(16).eq(b.take(16)));
+		assert!(!a.take(16).eq(c.take(16)));
+		assert!(a
+			.take(16)
+			.all(|addr| cidr.contains(&addr) && addr != Ipv6Addr::UNSPECIFIED));
+	}
+
+	#[test]
+	fn sample() {
+		let cidr = "192.0.2.0/24".parse::<Ipv4Cidr>().unwrap();
+		let iter = cidr.iter().sample(10, 7);
+		assert_eq!(iter.remaining(), NumberOfAddresses::Count(10));
+		assert_eq!(iter.size_hint(), (10, Some(10)));
+		assert!(iter.eq(cidr.iter().permuted(7).take(10)));
+		assert_eq!(cidr.iter().sample(1000, 7).count(), 256);
+
+		let cidr = "::/0".parse::<IpCidr>().unwrap();
+		assert_eq!(
+			cidr.iter().permuted(0).remaining(),
+			NumberOfAddresses::MaxIpv6Addresses
+		);
+		let mut iter = cidr.iter().sample(3, 0);
+		assert!(iter.next().is_some());
+		assert!(iter.next().is_some());
+		assert!(iter.next().is_some());
+		assert!(iter.next().is_none());
+	}
+}
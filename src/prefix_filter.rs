@@ -0,0 +1,598 @@
+//! Prefix lists as used in router configurations
+//!
+//! A [`PrefixFilter`] is a list of [`PrefixFilterEntry`]s ordered by
+//! sequence number; each entry matches networks within its prefix with a
+//! network length in a given range (`ge` / `le`).  The first matching
+//! entry decides whether a network is permitted or denied; networks not
+//! matched by any entry are denied.
+//!
+//! Filters can be parsed from (and are displayed as) Cisco-like prefix
+//! list entries, one per line:
+//!
+//! ```text
+//! ip prefix-list NAME seq 10 permit 10.0.0.0/8 ge 16 le 24
+//! seq 20 deny 0.0.0.0/0 le 32
+//! permit 2001:db8::/32 le 48
+//! ```
+//!
+//! The `ip prefix-list NAME` (or `ipv6 prefix-list NAME`) part is
+//! optional and the name is ignored (as are `description` entries);
+//! entries without `seq` get the highest sequence number so far plus 5.
+//! Juniper-like route filter lines are supported too:
+//!
+//! ```text
+//! route-filter 10.0.0.0/8 upto /24 accept;
+//! route-filter 192.0.2.0/24 exact reject;
+//! 198.51.100.0/24 prefix-length-range /26-/28 accept
+//! ```
+//!
+//! (with the match types `exact`, `orlonger`, `longer`, `upto /N` and
+//! `prefix-length-range /N-/M`).  Empty lines and lines starting with `!`
+//! or `#` are ignored.
+//!
+//! ```
+//! use cidr::{
+//!     prefix_filter::{FilterAction, PrefixFilter},
+//!     IpCidr,
+//! };
+//!
+//! let filter: PrefixFilter = "
+//!     seq 5 deny 10.1.0.0/16 le 32
+//!     seq 10 permit 10.0.0.0/8 ge 16 le 24
+//! "
+//! .parse()
+//! .unwrap();
+//! let cidr = |s: &str| s.parse::<IpCidr>().unwrap();
+//! assert!(filter.permits(&cidr("10.2.3.0/24")));
+//! assert!(!filter.permits(&cidr("10.1.3.0/24")));
+//! assert!(!filter.permits(&cidr("10.0.0.0/8")));
+//! assert_eq!(filter.evaluate(&cidr("10.0.0.0/8")), FilterAction::Deny);
+//! ```
+//!
+//! Requires the `alloc` feature.
+
+use alloc::vec::Vec;
+use core::{
+	fmt,
+	str::FromStr,
+};
+
+use crate::{
+	errors::{
+		PrefixFilterParseError,
+		PrefixFilterParseReason,
+	},
+	IpCidr,
+};
+
+/// Whether a matching network is permitted or denied
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum FilterAction {
+	/// Permit the network
+	Permit,
+	/// Deny the network
+	Deny,
+}
+
+impl fmt::Display for FilterAction {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Permit => write!(f, "permit"),
+			Self::Deny => write!(f, "deny"),
+		}
+	}
+}
+
+/// Entry in a [`PrefixFilter`]
+///
+/// Without `ge` and `le` only the prefix itself matches; with `ge` (and
+/// without `le`) networks within the prefix with at least `ge` bits
+/// match; with `le` (and without `ge`) networks within the prefix with
+/// at most `le` bits match.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct PrefixFilterEntry {
+	seq: u32,
+	action: FilterAction,
+	prefix: IpCidr,
+	ge: Option<u8>,
+	le: Option<u8>,
+}
+
+impl PrefixFilterEntry {
+	/// Create new entry
+	///
+	/// Returns `None` unless `prefix length <= ge <= le <= address
+	/// length` (for the given values).
+	pub const fn new(
+		seq: u32,
+		action: FilterAction,
+		prefix: IpCidr,
+		ge: Option<u8>,
+		le: Option<u8>,
+	) -> Option<Self> {
+		let min = match ge {
+			Some(ge) => ge,
+			None => prefix.network_length(),
+		};
+		let max = match le {
+			Some(le) => le,
+			None => prefix.family().len(),
+		};
+		if min < prefix.network_length() || min > max || max > prefix.family().len() {
+			return None;
+		}
+		Some(Self {
+			seq,
+			action,
+			prefix,
+			ge,
+			le,
+		})
+	}
+
+	/// sequence number
+	pub const fn seq(&self) -> u32 {
+		self.seq
+	}
+
+	/// action for matching networks
+	pub const fn action(&self) -> FilterAction {
+		self.action
+	}
+
+	/// prefix matching networks are contained in
+	pub const fn prefix(&self) -> IpCidr {
+		self.prefix
+	}
+
+	/// minimum network length (`ge`) if given
+	pub const fn ge(&self) -> Option<u8> {
+		self.ge
+	}
+
+	/// maximum network length (`le`) if given
+	pub const fn le(&self) -> Option<u8> {
+		self.le
+	}
+
+	/// range of network lengths of matching networks
+	pub const fn length_range(&self) -> (u8, u8) {
+		match (self.ge, self.le) {
+			(None, None) => (self.prefix.network_length(), self.prefix.network_length()),
+			(Some(ge), None) => (ge, self.prefix.family().len()),
+			(None, Some(le)) => (self.prefix.network_length(), le),
+			(Some(ge), Some(le)) => (ge, le),
+		}
+	}
+
+	/// whether the network matches the entry
+	pub const fn matches(&self, network: &IpCidr) -> bool {
+		let (min, max) = self.length_range();
+		let len = network.network_length();
+		min <= len
+			&& len <= max
+			&& self.prefix.contains(&network.first_address())
+			&& self.prefix.network_length() <= len
+	}
+
+	// parse a line; `None` for lines without entry
+	fn parse_line(
+		line: &str,
+		line_no: usize,
+		default_seq: u32,
+	) -> Result<Option<Self>, PrefixFilterParseError> {
+		let line = line.trim();
+		if line.is_empty() || line.starts_with('!') || line.starts_with('#') {
+			return Ok(None);
+		}
+		let error = |reason| PrefixFilterParseError::new(line_no, reason);
+		let mut tokens = line.trim_end_matches(';').split_whitespace();
+		let mut next = || {
+			tokens
+				.next()
+				.ok_or(error(PrefixFilterParseReason::UnexpectedEnd))
+		};
+
+		let mut token = next()?;
+		if token == "ip" || token == "ipv6" {
+			if next()? != "prefix-list" {
+				return Err(error(PrefixFilterParseReason::InvalidSyntax));
+			}
+			let _name = next()?;
+			token = next()?;
+			if token == "description" {
+				return Ok(None);
+			}
+		} else if token == "route-filter" {
+			token = next()?;
+		}
+		let mut seq = default_seq;
+		if token == "seq" {
+			seq = parse_number(next()?).ok_or(error(PrefixFilterParseReason::InvalidSyntax))?;
+			token = next()?;
+		}
+
+		let parse_prefix = |s: &str| {
+			s.parse::<IpCidr>()
+				.map_err(|e| error(PrefixFilterParseReason::NetworkParseError(e)))
+		};
+		let parse_length =
+			|s: &str| parse_number(s).ok_or(error(PrefixFilterParseReason::InvalidSyntax));
+		let (action, prefix, ge, le);
+		if let Some(a) = parse_action(token) {
+			// Cisco: ACTION PREFIX [ge N] [le N]
+			action = a;
+			prefix = parse_prefix(next()?)?;
+			let mut token = tokens.next();
+			ge = match token {
+				Some("ge") => {
+					let ge = parse_length(
+						tokens
+							.next()
+							.ok_or(error(PrefixFilterParseReason::UnexpectedEnd))?,
+					)?;
+					token = tokens.next();
+					Some(ge)
+				},
+				_ => None,
+			};
+			le = match token {
+				Some("le") => {
+					let le = parse_length(
+						tokens
+							.next()
+							.ok_or(error(PrefixFilterParseReason::UnexpectedEnd))?,
+					)?;
+					token = tokens.next();
+					Some(le)
+				},
+				_ => None,
+			};
+			if token.is_some() {
+				return Err(error(PrefixFilterParseReason::TrailingData));
+			}
+		} else {
+			// Juniper: PREFIX MATCH-TYPE ACTION
+			prefix = parse_prefix(token)?;
+			let max = prefix.family().len();
+			let slash_length = |s: &str| match s.strip_prefix('/') {
+				Some(s) => parse_length(s),
+				None => Err(error(PrefixFilterParseReason::InvalidSyntax)),
+			};
+			(ge, le) = match next()? {
+				"exact" => (None, None),
+				"orlonger" => (None, Some(max)),
+				"longer" => {
+					let ge = prefix
+						.network_length()
+						.checked_add(1)
+						.filter(|&ge| ge <= max)
+						.ok_or(error(PrefixFilterParseReason::InvalidLengthRange))?;
+					(Some(ge), None)
+				},
+				"upto" => (None, Some(slash_length(next()?)?)),
+				"prefix-length-range" => {
+					let (min, max) = next()?
+						.split_once('-')
+						.ok_or(error(PrefixFilterParseReason::InvalidSyntax))?;
+					(Some(slash_length(min)?), Some(slash_length(max)?))
+				},
+				_ => return Err(error(PrefixFilterParseReason::InvalidSyntax)),
+			};
+			action = match next()? {
+				"accept" => FilterAction::Permit,
+				"reject" => FilterAction::Deny,
+				a => parse_action(a).ok_or(error(PrefixFilterParseReason::InvalidSyntax))?,
+			};
+			if tokens.next().is_some() {
+				return Err(error(PrefixFilterParseReason::TrailingData));
+			}
+		}
+		Self::new(seq, action, prefix, ge, le)
+			.map(Some)
+			.ok_or(error(PrefixFilterParseReason::InvalidLengthRange))
+	}
+}
+
+fn parse_action(s: &str) -> Option<FilterAction> {
+	match s {
+		"permit" => Some(FilterAction::Permit),
+		"deny" => Some(FilterAction::Deny),
+		_ => None,
+	}
+}
+
+fn parse_number<T: FromStr>(s: &str) -> Option<T> {
+	s.parse().ok()
+}
+
+impl fmt::Display for PrefixFilterEntry {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "seq {} {} {}", self.seq, self.action, self.prefix)?;
+		if let Some(ge) = self.ge {
+			write!(f, " ge {}", ge)?;
+		}
+		if let Some(le) = self.le {
+			write!(f, " le {}", le)?;
+		}
+		Ok(())
+	}
+}
+
+/// Parses a single entry (see the [module documentation](self) for the
+/// syntax); the sequence number defaults to 5.
+impl FromStr for PrefixFilterEntry {
+	type Err = PrefixFilterParseError;
+
+	fn from_str(s: &str) -> Result<Self, PrefixFilterParseError> {
+		if s.lines().count() > 1 {
+			return Err(PrefixFilterParseError::new(
+				2,
+				PrefixFilterParseReason::TrailingData,
+			));
+		}
+		Self::parse_line(s, 1, 5)?.ok_or(PrefixFilterParseError::new(
+			1,
+			PrefixFilterParseReason::UnexpectedEnd,
+		))
+	}
+}
+
+/// List of entries ordered by sequence number; the first matching entry
+/// decides (see the [module documentation](self))
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct PrefixFilter {
+	entries: Vec<PrefixFilterEntry>,
+}
+
+impl PrefixFilter {
+	/// Create new empty filter (denying everything)
+	pub const fn new() -> Self {
+		Self {
+			entries: Vec::new(),
+		}
+	}
+
+	/// Insert entry, replacing (and returning) an entry with the same
+	/// sequence number
+	pub fn insert(&mut self, entry: PrefixFilterEntry) -> Option<PrefixFilterEntry> {
+		match self.entries.binary_search_by_key(&entry.seq, |e| e.seq) {
+			Ok(pos) => Some(core::mem::replace(&mut self.entries[pos], entry)),
+			Err(pos) => {
+				self.entries.insert(pos, entry);
+				None
+			},
+		}
+	}
+
+	/// Remove entry with the given sequence number
+	pub fn remove(&mut self, seq: u32) -> Option<PrefixFilterEntry> {
+		let pos = self.entries.binary_search_by_key(&seq, |e| e.seq).ok()?;
+		Some(self.entries.remove(pos))
+	}
+
+	/// Entries ordered by sequence number
+	pub fn entries(&self) -> &[PrefixFilterEntry] {
+		&self.entries
+	}
+
+	/// Sequence number used for entries without one when parsing: the
+	/// highest sequence number plus 5 (saturating)
+	pub fn next_seq(&self) -> u32 {
+		match self.entries.last() {
+			Some(entry) => entry.seq.saturating_add(5),
+			None => 5,
+		}
+	}
+
+	/// First entry matching the network
+	pub fn matching_entry(&self, network: &IpCidr) -> Option<&PrefixFilterEntry> {
+		self.entries.iter().find(|entry| entry.matches(network))
+	}
+
+	/// Action of the first entry matching the network, [`Deny`] if none
+	/// matches
+	///
+	/// [`Deny`]: FilterAction::Deny
+	pub fn evaluate(&self, network: &IpCidr) -> FilterAction {
+		match self.matching_entry(network) {
+			Some(entry) => entry.action,
+			None => FilterAction::Deny,
+		}
+	}
+
+	/// Whether the network is permitted
+	pub fn permits(&self, network: &IpCidr) -> bool {
+		self.evaluate(network) == FilterAction::Permit
+	}
+}
+
+/// Displays one entry per line
+impl fmt::Display for PrefixFilter {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for entry in &self.entries {
+			writeln!(f, "{}", entry)?;
+		}
+		Ok(())
+	}
+}
+
+/// Parses one entry per line (see the [module documentation](self)
+/// for the syntax); later entries replace earlier entries with the same
+/// sequence number.
+impl FromStr for PrefixFilter {
+	type Err = PrefixFilterParseError;
+
+	fn from_str(s: &str) -> Result<Self, PrefixFilterParseError> {
+		let mut filter = Self::new();
+		for (index, line) in s.lines().enumerate() {
+			if let Some(entry) = PrefixFilterEntry::parse_line(line, index + 1, filter.next_seq())?
+			{
+				filter.insert(entry);
+			}
+		}
+		Ok(filter)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use alloc::string::ToString;
+
+	use super::*;
+	use crate::errors::ErrorKind;
+
+	fn cidr(s: &str) -> IpCidr {
+		s.parse().unwrap()
+	}
+
+	fn entry(s: &str) -> PrefixFilterEntry {
+		s.parse().unwrap()
+	}
+
+	#[test]
+	fn matching() {
+		let exact = entry("permit 10.0.0.0/8");
+		assert!(exact.matches(&cidr("10.0.0.0/8")));
+		assert!(!exact.matches(&cidr("10.0.0.0/9")));
+		assert!(!exact.matches(&cidr("10.0.0.0/7")));
+
+		let ge = entry("permit 10.0.0.0/8 ge 16");
+		assert_eq!(ge.length_range(), (16, 32));
+		assert!(ge.matches(&cidr("10.1.0.0/16")));
+		assert!(ge.matches(&cidr("10.1.2.3/32")));
+		assert!(!ge.matches(&cidr("10.0.0.0/15")));
+		assert!(!ge.matches(&cidr("11.1.0.0/16")));
+
+		let le = entry("permit 10.0.0.0/8 le 16");
+		assert_eq!(le.length_range(), (8, 16));
+		assert!(le.matches(&cidr("10.0.0.0/8")));
+		assert!(!le.matches(&cidr("10.1.1.0/24")));
+
+		let range = entry("permit 10.0.0.0/8 ge 16 le 24");
+		assert!(range.matches(&cidr("10.1.1.0/24")));
+		assert!(!range.matches(&cidr("10.1.1.0/25")));
+
+		let default = entry("permit 0.0.0.0/0 le 32");
+		assert!(default.matches(&cidr("192.0.2.1/32")));
+		assert!(!default.matches(&cidr("::/0")));
+	}
+
+	#[test]
+	fn first_match() {
+		let mut filter = PrefixFilter::new();
+		assert!(!filter.permits(&cidr("10.0.0.0/8")));
+		filter.insert(entry("seq 20 permit 10.0.0.0/8 le 24"));
+		filter.insert(entry("seq 10 deny 10.1.0.0/16 le 32"));
+		assert_eq!(filter.next_seq(), 25);
+		assert!(filter.permits(&cidr("10.2.0.0/16")));
+		assert!(!filter.permits(&cidr("10.1.0.0/24")));
+		assert_eq!(
+			filter.matching_entry(&cidr("10.1.0.0/24")).map(|e| e.seq()),
+			Some(10)
+		);
+		assert_eq!(filter.remove(10).map(|e| e.seq()), Some(10));
+		assert!(filter.permits(&cidr("10.1.0.0/24")));
+		assert_eq!(
+			filter
+				.insert(entry("seq 20 deny 10.0.0.0/8 le 24"))
+				.map(|e| e.action()),
+			Some(FilterAction::Permit)
+		);
+		assert_eq!(filter.evaluate(&cidr("10.1.0.0/24")), FilterAction::Deny);
+	}
+
+	#[test]
+	fn parse_cisco() {
+		let filter: PrefixFilter = "
+			! comment
+			ip prefix-list TEST description test list
+			ip prefix-list TEST seq 10 permit 10.0.0.0/8 ge 16 le 24
+			ip prefix-list TEST deny 192.0.2.0/24
+			ipv6 prefix-list TEST6 permit 2001:db8::/32 le 48
+			seq 12 permit 0.0.0.0/0
+		"
+		.parse()
+		.unwrap();
+		assert_eq!(
+			filter.to_string(),
+			"seq 10 permit 10.0.0.0/8 ge 16 le 24\n\
+			 seq 12 permit 0.0.0.0/0\n\
+			 seq 15 deny 192.0.2.0/24\n\
+			 seq 20 permit 2001:db8::/32 le 48\n"
+		);
+		assert_eq!(filter.to_string().parse::<PrefixFilter>().unwrap(), filter);
+	}
+
+	#[test]
+	fn parse_juniper() {
+		let filter: PrefixFilter = "
+			route-filter 10.0.0.0/8 upto /24 accept;
+			route-filter 192.0.2.0/24 exact reject;
+			route-filter 198.51.100.0/24 longer accept;
+			0.0.0.0/0 orlonger reject
+			2001:db8::/32 prefix-length-range /40-/48 accept
+		"
+		.parse()
+		.unwrap();
+		let entries = filter.entries();
+		assert_eq!(entries[0], entry("seq 5 permit 10.0.0.0/8 le 24"));
+		assert_eq!(entries[1], entry("seq 10 deny 192.0.2.0/24"));
+		assert_eq!(entries[2], entry("seq 15 permit 198.51.100.0/24 ge 25"));
+		assert_eq!(entries[3], entry("seq 20 deny 0.0.0.0/0 le 32"));
+		assert_eq!(entries[4], entry("seq 25 permit 2001:db8::/32 ge 40 le 48"));
+	}
+
+	#[test]
+	fn parse_errors() {
+		let kind = |s: &str| s.parse::<PrefixFilter>().unwrap_err().kind();
+		assert_eq!(kind("permit"), ErrorKind::UnexpectedEnd);
+		assert_eq!(kind("permit 10.0.0.0/8 ge"), ErrorKind::UnexpectedEnd);
+		assert_eq!(kind("permit 10.0.0.0/8 foo"), ErrorKind::TrailingData);
+		assert_eq!(
+			kind("permit 10.0.0.0/8 le 24 ge 16"),
+			ErrorKind::TrailingData
+		);
+		assert_eq!(kind("allow 10.0.0.0/8"), ErrorKind::InvalidAddress);
+		assert_eq!(kind("seq x permit 10.0.0.0/8"), ErrorKind::InvalidSyntax);
+		assert_eq!(kind("10.0.0.0/8 upto 24 accept"), ErrorKind::InvalidSyntax);
+		assert_eq!(kind("10.0.0.0/8 exact drop"), ErrorKind::InvalidSyntax);
+		assert_eq!(kind("permit 10.0.0.1/8"), ErrorKind::InvalidHostPart);
+		assert_eq!(
+			kind("permit 10.0.0.0/8 ge 4"),
+			ErrorKind::InvalidNetworkLength
+		);
+		assert_eq!(
+			kind("permit 10.0.0.0/8 le 33"),
+			ErrorKind::InvalidNetworkLength
+		);
+		assert_eq!(
+			kind("permit 10.0.0.0/8 ge 24 le 16"),
+			ErrorKind::InvalidNetworkLength
+		);
+		assert_eq!(
+			kind("1.2.3.4/32 longer accept"),
+			ErrorKind::InvalidNetworkLength
+		);
+
+		let err = "permit 10.0.0.0/8\n\npermit 10.0.0.0/33"
+			.parse::<PrefixFilter>()
+			.unwrap_err();
+		assert_eq!(err.line(), 3);
+		assert_eq!(
+			err.to_string(),
+			"line 3: invalid length for network: Network length 33 is too long for Ipv4 (maximum: 32)"
+		);
+		assert_eq!(
+			"".parse::<PrefixFilterEntry>().unwrap_err().kind(),
+			ErrorKind::UnexpectedEnd
+		);
+		assert_eq!(
+			"permit ::/0\npermit ::/0"
+				.parse::<PrefixFilterEntry>()
+				.unwrap_err()
+				.kind(),
+			ErrorKind::TrailingData
+		);
+	}
+}
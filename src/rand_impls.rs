@@ -0,0 +1,268 @@
+#![cfg(feature = "rand")]
+
+// random addresses within networks / ranges and random subnets
+//
+// `Cidr` and `InetPair` types are distributions over the addresses they
+// cover: sampling returns a uniformly distributed address (or host within
+// the network).
+
+use core::net::{
+	IpAddr,
+	Ipv4Addr,
+	Ipv6Addr,
+};
+
+use rand::{
+	distr::Distribution,
+	Rng,
+};
+
+use crate::{
+	internal_traits::PrivInetPair,
+	num::NumberOfAddresses,
+	IpCidr,
+	IpInet,
+	IpInetPair,
+	Ipv4Cidr,
+	Ipv4Inet,
+	Ipv4InetPair,
+	Ipv6Cidr,
+	Ipv6Inet,
+	Ipv6InetPair,
+};
+
+// uniformly distributed offset for a range of `count` addresses
+fn random_offset<R: Rng + ?Sized>(rng: &mut R, count: NumberOfAddresses) -> u128 {
+	match count {
+		NumberOfAddresses::Count(c) => rng.random_range(0..c),
+		NumberOfAddresses::MaxIpv6Addresses => rng.random(),
+	}
+}
+
+macro_rules! impl_rand_for {
+	($cidr:ident : inet $inet:ident : pair $pair:ident : addr $addr:ident) => {
+		/// Uniformly distributed host within the network
+		impl Distribution<$inet> for $cidr {
+			fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $inet {
+				self._range_pair().sample(rng)
+			}
+		}
+
+		/// Uniformly distributed address within the network
+		impl Distribution<$addr> for $cidr {
+			fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $addr {
+				self._range_pair().sample(rng)
+			}
+		}
+
+		/// Uniformly distributed host within `first..=second`
+		impl Distribution<$inet> for $pair {
+			fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $inet {
+				let offset = random_offset(rng, self._covered_addresses());
+				self.first().overflowing_add(offset).0
+			}
+		}
+
+		/// Uniformly distributed address within `first..=second`
+		impl Distribution<$addr> for $pair {
+			fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $addr {
+				let inet: $inet = self.sample(rng);
+				inet.address()
+			}
+		}
+
+		impl $cidr {
+			/// Uniformly distributed subnet with network length `len`
+			/// within the network
+			///
+			/// Returns `None` if `len` is shorter than the network length
+			/// or longer than the address length.
+			pub fn random_subnet<R: Rng + ?Sized>(&self, len: u8, rng: &mut R) -> Option<Self> {
+				if len < self.network_length || len > self.family().len() {
+					return None;
+				}
+				let subnet_bits = (len - self.network_length) as u32;
+				let host_bits = (self.family().len() - len) as u32;
+				let count = NumberOfAddresses::count_from_distance(
+					u128::MAX.checked_shr(128 - subnet_bits).unwrap_or(0),
+				);
+				let index = random_offset(rng, count);
+				// `index` is always 0 if `host_bits` is 128
+				let offset = index.checked_shl(host_bits).unwrap_or(0);
+				let address = self.first().overflowing_add(offset).0.address();
+				Some(Self {
+					address,
+					network_length: len,
+				})
+			}
+		}
+	};
+}
+
+impl_rand_for! {Ipv4Cidr : inet Ipv4Inet : pair Ipv4InetPair : addr Ipv4Addr}
+impl_rand_for! {Ipv6Cidr : inet Ipv6Inet : pair Ipv6InetPair : addr Ipv6Addr}
+
+/// Uniformly distributed host within the network
+impl Distribution<IpInet> for IpCidr {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> IpInet {
+		self._range_pair().sample(rng)
+	}
+}
+
+/// Uniformly distributed address within the network
+impl Distribution<IpAddr> for IpCidr {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> IpAddr {
+		self._range_pair().sample(rng)
+	}
+}
+
+/// Uniformly distributed host within `first..=second`
+impl Distribution<IpInet> for IpInetPair {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> IpInet {
+		match self {
+			Self::V4(p) => IpInet::V4(p.sample(rng)),
+			Self::V6(p) => IpInet::V6(p.sample(rng)),
+		}
+	}
+}
+
+/// Uniformly distributed address within `first..=second`
+impl Distribution<IpAddr> for IpInetPair {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> IpAddr {
+		match self {
+			Self::V4(p) => IpAddr::V4(p.sample(rng)),
+			Self::V6(p) => IpAddr::V6(p.sample(rng)),
+		}
+	}
+}
+
+impl IpCidr {
+	/// Uniformly distributed subnet with network length `len` within the
+	/// network
+	///
+	/// Returns `None` if `len` is shorter than the network length or
+	/// longer than the address length.
+	pub fn random_subnet<R: Rng + ?Sized>(&self, len: u8, rng: &mut R) -> Option<Self> {
+		match self {
+			Self::V4(c) => Some(Self::V4(c.random_subnet(len, rng)?)),
+			Self::V6(c) => Some(Self::V6(c.random_subnet(len, rng)?)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::net::{
+		IpAddr,
+		Ipv4Addr,
+		Ipv6Addr,
+	};
+
+	use rand::{
+		Rng,
+		RngCore,
+	};
+
+	use crate::{
+		IpCidr,
+		IpInet,
+		Ipv4Cidr,
+		Ipv4Inet,
+		Ipv4InetPair,
+		Ipv6Cidr,
+		Ipv6Inet,
+	};
+
+	// xorshift64*; good enough for tests and doesn't need extra features
+	struct TestRng(u64);
+
+	impl RngCore for TestRng {
+		fn next_u32(&mut self) -> u32 {
+			(self.next_u64() >> 32) as u32
+		}
+
+		fn next_u64(&mut self) -> u64 {
+			self.0 ^= self.0 >> 12;
+			self.0 ^= self.0 << 25;
+			self.0 ^= self.0 >> 27;
+			self.0.wrapping_mul(0x2545f4914f6cdd1d)
+		}
+
+		fn fill_bytes(&mut self, dst: &mut [u8]) {
+			for chunk in dst.chunks_mut(8) {
+				let bytes = self.next_u64().to_le_bytes();
+				chunk.copy_from_slice(&bytes[..chunk.len()]);
+			}
+		}
+	}
+
+	#[test]
+	fn addresses_in_network() {
+		let mut rng = TestRng(0x1234_5678);
+		let cidr = "192.0.2.0/28".parse::<Ipv4Cidr>().unwrap();
+		let mut seen = [false; 16];
+		for _ in 0..1000 {
+			let addr: Ipv4Addr = rng.sample(cidr);
+			seen[cidr.index_of(&addr).unwrap() as usize] = true;
+			let inet: Ipv4Inet = rng.sample(cidr);
+			assert_eq!(inet.network(), cidr);
+		}
+		assert!(seen.iter().all(|&s| s), "not all addresses generated");
+
+		let cidr = "2001:db8::/64".parse::<Ipv6Cidr>().unwrap();
+		for _ in 0..100 {
+			let addr: Ipv6Addr = rng.sample(cidr);
+			assert!(cidr.contains(&addr));
+			let inet: Ipv6Inet = rng.sample(cidr);
+			assert_eq!(inet.network(), cidr);
+		}
+
+		let cidr = "::/0".parse::<IpCidr>().unwrap();
+		let addr: IpAddr = rng.sample(cidr);
+		assert!(addr.is_ipv6());
+		let cidr = "192.0.2.1/32".parse::<IpCidr>().unwrap();
+		let inet: IpInet = rng.sample(cidr);
+		assert_eq!(inet, "192.0.2.1/32".parse().unwrap());
+	}
+
+	#[test]
+	fn addresses_in_pair() {
+		let mut rng = TestRng(0x1234_5678);
+		let pair = Ipv4InetPair::new(
+			"192.0.2.10/24".parse().unwrap(),
+			"192.0.2.12/24".parse().unwrap(),
+		)
+		.unwrap();
+		let mut seen = [false; 3];
+		for _ in 0..100 {
+			let addr: Ipv4Addr = rng.sample(pair);
+			seen[(addr.octets()[3] - 10) as usize] = true;
+		}
+		assert_eq!(seen, [true; 3]);
+	}
+
+	#[test]
+	fn subnets() {
+		let mut rng = TestRng(0x1234_5678);
+		let cidr = "10.0.0.0/8".parse::<Ipv4Cidr>().unwrap();
+		for _ in 0..100 {
+			let subnet = cidr.random_subnet(24, &mut rng).unwrap();
+			assert_eq!(subnet.network_length(), 24);
+			assert!(cidr.contains(&subnet.first_address()));
+		}
+		assert_eq!(cidr.random_subnet(8, &mut rng), Some(cidr));
+		assert_eq!(cidr.random_subnet(7, &mut rng), None);
+		assert_eq!(cidr.random_subnet(33, &mut rng), None);
+
+		let cidr = "::/0".parse::<IpCidr>().unwrap();
+		let subnet = cidr.random_subnet(128, &mut rng).unwrap();
+		assert!(subnet.is_host_address());
+		let subnet = cidr.random_subnet(64, &mut rng).unwrap();
+		assert_eq!(subnet.network_length(), 64);
+		assert_eq!(cidr.random_subnet(0, &mut rng), Some(cidr));
+		let cidr = "::/0".parse::<Ipv6Cidr>().unwrap();
+		assert_eq!(cidr.random_subnet(0, &mut rng), Some(cidr));
+		let cidr = "0.0.0.0/0".parse::<Ipv4Cidr>().unwrap();
+		assert_eq!(cidr.random_subnet(0, &mut rng), Some(cidr));
+	}
+}
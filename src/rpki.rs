@@ -0,0 +1,461 @@
+//! RPKI route origin validation (RFC 6811)
+//!
+//! A [`RoaTable`] holds validated ROA payloads ([`Roa`]: prefix, max
+//! length and origin AS) and classifies announced routes as
+//! [`Valid`](RouteOriginValidity::Valid),
+//! [`Invalid`](RouteOriginValidity::Invalid) or
+//! [`NotFound`](RouteOriginValidity::NotFound).
+//!
+//! Tables can be loaded from the CSV and JSON exports of common relying
+//! party software (Routinator, rpki-client, ...): CSV rows start with
+//! the columns `ASN,IP Prefix,Max Length` (further columns like the trust
+//! anchor are ignored, as is a header row); JSON documents contain an
+//! array `roas` of objects with the fields `asn`, `prefix` and
+//! `maxLength`.  AS numbers can be given with or without `AS` prefix, an
+//! empty (or missing) max length defaults to the prefix length.
+//!
+//! ```
+//! use cidr::rpki::{RoaTable, RouteOriginValidity};
+//!
+//! let table = RoaTable::parse_csv(
+//!     "ASN,IP Prefix,Max Length,Trust Anchor\n\
+//!      AS64496,192.0.2.0/24,24,ripe\n",
+//! )
+//! .unwrap();
+//! let validate = |prefix: &str, origin| table.validate(&prefix.parse().unwrap(), origin);
+//! assert_eq!(validate("192.0.2.0/24", 64496), RouteOriginValidity::Valid);
+//! assert_eq!(validate("192.0.2.0/24", 64497), RouteOriginValidity::Invalid);
+//! assert_eq!(validate("192.0.2.0/25", 64496), RouteOriginValidity::Invalid);
+//! assert_eq!(validate("198.51.100.0/24", 64496), RouteOriginValidity::NotFound);
+//! ```
+//!
+//! Requires the `alloc` feature; loading from files requires `std`, JSON
+//! parsing the `serde_json` feature.
+
+use alloc::collections::BTreeSet;
+
+use crate::{
+	errors::{
+		RoaParseError,
+		RoaParseReason,
+	},
+	IpCidr,
+	IpInet,
+};
+
+/// Validated ROA payload: routes for networks within `prefix` up to
+/// `max_length` may be originated by `asn`
+///
+/// ROAs for AS 0 (RFC 6483) never match a route.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Roa {
+	prefix: IpCidr,
+	max_length: u8,
+	asn: u32,
+}
+
+impl Roa {
+	/// Create new ROA
+	///
+	/// Returns `None` unless `prefix length <= max_length <= address
+	/// length`.
+	pub const fn new(prefix: IpCidr, max_length: u8, asn: u32) -> Option<Self> {
+		if max_length < prefix.network_length() || max_length > prefix.family().len() {
+			return None;
+		}
+		Some(Self {
+			prefix,
+			max_length,
+			asn,
+		})
+	}
+
+	/// prefix
+	pub const fn prefix(&self) -> IpCidr {
+		self.prefix
+	}
+
+	/// maximum network length of matching routes
+	pub const fn max_length(&self) -> u8 {
+		self.max_length
+	}
+
+	/// AS number allowed to originate matching routes
+	pub const fn asn(&self) -> u32 {
+		self.asn
+	}
+
+	/// whether the route prefix is within the ROA prefix ("covered")
+	pub const fn covers(&self, prefix: &IpCidr) -> bool {
+		self.prefix.network_length() <= prefix.network_length()
+			&& self.prefix.contains(&prefix.first_address())
+	}
+
+	/// whether the ROA covers the route prefix and allows it to be
+	/// originated by `origin` ("matched")
+	pub const fn matches(&self, prefix: &IpCidr, origin: u32) -> bool {
+		self.asn != 0
+			&& self.asn == origin
+			&& prefix.network_length() <= self.max_length
+			&& self.covers(prefix)
+	}
+}
+
+/// Validation state of a route (RFC 6811)
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum RouteOriginValidity {
+	/// A ROA matches the route
+	Valid,
+	/// At least one ROA covers the route, but none matches
+	Invalid,
+	/// No ROA covers the route
+	NotFound,
+}
+
+/// Set of [`Roa`]s to validate routes with
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct RoaTable {
+	roas: BTreeSet<Roa>,
+}
+
+impl RoaTable {
+	/// Create new empty table
+	pub const fn new() -> Self {
+		Self {
+			roas: BTreeSet::new(),
+		}
+	}
+
+	/// Insert ROA; returns `false` if it already was present
+	pub fn insert(&mut self, roa: Roa) -> bool {
+		self.roas.insert(roa)
+	}
+
+	/// Remove ROA; returns `false` if it wasn't present
+	pub fn remove(&mut self, roa: &Roa) -> bool {
+		self.roas.remove(roa)
+	}
+
+	/// Number of ROAs
+	pub fn len(&self) -> usize {
+		self.roas.len()
+	}
+
+	/// Whether the table is empty
+	pub fn is_empty(&self) -> bool {
+		self.roas.is_empty()
+	}
+
+	/// All ROAs (ordered)
+	pub fn iter(&self) -> impl Iterator<Item = &Roa> + '_ {
+		self.roas.iter()
+	}
+
+	/// ROAs covering the route prefix, least specific first
+	pub fn covering(&self, prefix: &IpCidr) -> impl Iterator<Item = &Roa> + '_ {
+		let prefix = *prefix;
+		(0..=prefix.network_length()).flat_map(move |len| {
+			let network = IpInet::new(prefix.first_address(), len)
+				.expect("valid network length")
+				.network();
+			let first = Roa {
+				prefix: network,
+				max_length: 0,
+				asn: 0,
+			};
+			let last = Roa {
+				prefix: network,
+				max_length: u8::MAX,
+				asn: u32::MAX,
+			};
+			self.roas.range(first..=last)
+		})
+	}
+
+	/// Validate route for `prefix` originated by `origin` (RFC 6811)
+	pub fn validate(&self, prefix: &IpCidr, origin: u32) -> RouteOriginValidity {
+		let mut covered = false;
+		for roa in self.covering(prefix) {
+			if roa.matches(prefix, origin) {
+				return RouteOriginValidity::Valid;
+			}
+			covered = true;
+		}
+		if covered {
+			RouteOriginValidity::Invalid
+		} else {
+			RouteOriginValidity::NotFound
+		}
+	}
+
+	/// Parse CSV export (see [module documentation](self))
+	pub fn parse_csv(input: &str) -> Result<Self, RoaParseError> {
+		let mut table = Self::new();
+		for (index, line) in input.lines().enumerate() {
+			let line = line.trim();
+			if line.is_empty() {
+				continue;
+			}
+			let error = |reason| RoaParseError::new(index + 1, reason);
+			let mut fields = line.split(',').map(str::trim);
+			let asn = fields.next().unwrap_or_default();
+			if asn.eq_ignore_ascii_case("asn") {
+				// header
+				continue;
+			}
+			let asn = parse_asn(asn).ok_or(error(RoaParseReason::InvalidAsn))?;
+			let prefix = fields.next().ok_or(error(RoaParseReason::InvalidSyntax))?;
+			let max_length = match fields.next() {
+				None | Some("") => None,
+				Some(max_length) => Some(
+					max_length
+						.parse()
+						.map_err(|_| error(RoaParseReason::InvalidMaxLength))?,
+				),
+			};
+			table.insert(make_roa(asn, prefix, max_length).map_err(error)?);
+		}
+		Ok(table)
+	}
+
+	/// Parse JSON export (see [module documentation](self))
+	#[cfg(feature = "serde_json")]
+	pub fn parse_json(input: &str) -> Result<Self, RoaParseError> {
+		use serde_json::Value;
+
+		let invalid = RoaParseError::new(0, RoaParseReason::InvalidSyntax);
+		let document: Value = serde_json::from_str(input).map_err(|_| invalid.clone())?;
+		let roas = document
+			.get("roas")
+			.and_then(Value::as_array)
+			.ok_or(invalid)?;
+		let mut table = Self::new();
+		for (index, roa) in roas.iter().enumerate() {
+			let error = |reason| RoaParseError::new(index + 1, reason);
+			let asn = match roa.get("asn") {
+				Some(Value::String(asn)) => parse_asn(asn),
+				Some(Value::Number(asn)) => asn.as_u64().and_then(|asn| u32::try_from(asn).ok()),
+				_ => None,
+			};
+			let asn = asn.ok_or(error(RoaParseReason::InvalidAsn))?;
+			let prefix = roa
+				.get("prefix")
+				.and_then(Value::as_str)
+				.ok_or(error(RoaParseReason::InvalidSyntax))?;
+			let max_length = match roa.get("maxLength") {
+				None | Some(Value::Null) => None,
+				Some(max_length) => Some(
+					max_length
+						.as_u64()
+						.ok_or(error(RoaParseReason::InvalidMaxLength))?,
+				),
+			};
+			table.insert(make_roa(asn, prefix, max_length).map_err(error)?);
+		}
+		Ok(table)
+	}
+
+	/// Load CSV export from file (see [module documentation](self))
+	///
+	/// Parse errors are returned as [`std::io::ErrorKind::InvalidData`]
+	/// wrapping the [`RoaParseError`].
+	#[cfg(feature = "std")]
+	pub fn load_csv<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+		let input = std::fs::read_to_string(path)?;
+		Self::parse_csv(&input).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+	}
+
+	/// Load JSON export from file (see [module documentation](self))
+	///
+	/// Parse errors are returned as [`std::io::ErrorKind::InvalidData`]
+	/// wrapping the [`RoaParseError`].
+	#[cfg(all(feature = "std", feature = "serde_json"))]
+	pub fn load_json<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+		let input = std::fs::read_to_string(path)?;
+		Self::parse_json(&input)
+			.map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+	}
+}
+
+impl FromIterator<Roa> for RoaTable {
+	fn from_iter<I: IntoIterator<Item = Roa>>(iter: I) -> Self {
+		Self {
+			roas: iter.into_iter().collect(),
+		}
+	}
+}
+
+impl Extend<Roa> for RoaTable {
+	fn extend<I: IntoIterator<Item = Roa>>(&mut self, iter: I) {
+		self.roas.extend(iter)
+	}
+}
+
+// "AS64496" or "64496"
+fn parse_asn(s: &str) -> Option<u32> {
+	let digits = match s.get(..2) {
+		Some(prefix) if prefix.eq_ignore_ascii_case("as") => &s[2..],
+		_ => s,
+	};
+	digits.parse().ok()
+}
+
+fn make_roa(asn: u32, prefix: &str, max_length: Option<u64>) -> Result<Roa, RoaParseReason> {
+	let prefix: IpCidr = prefix.parse().map_err(RoaParseReason::NetworkParseError)?;
+	let max_length = match max_length {
+		Some(max_length) => {
+			u8::try_from(max_length).map_err(|_| RoaParseReason::InvalidMaxLength)?
+		},
+		None => prefix.network_length(),
+	};
+	Roa::new(prefix, max_length, asn).ok_or(RoaParseReason::InvalidMaxLength)
+}
+
+#[cfg(test)]
+mod tests {
+	use alloc::vec::Vec;
+
+	use super::*;
+	use crate::errors::ErrorKind;
+
+	fn cidr(s: &str) -> IpCidr {
+		s.parse().unwrap()
+	}
+
+	fn roa(prefix: &str, max_length: u8, asn: u32) -> Roa {
+		Roa::new(cidr(prefix), max_length, asn).unwrap()
+	}
+
+	#[test]
+	fn validate() {
+		let table: RoaTable = [
+			roa("10.0.0.0/8", 16, 64496),
+			roa("10.1.0.0/16", 24, 64497),
+			roa("192.0.2.0/24", 24, 0),
+			roa("2001:db8::/32", 48, 64496),
+		]
+		.into_iter()
+		.collect();
+		let validate = |prefix: &str, origin| table.validate(&cidr(prefix), origin);
+
+		assert_eq!(validate("10.0.0.0/8", 64496), RouteOriginValidity::Valid);
+		assert_eq!(validate("10.2.0.0/16", 64496), RouteOriginValidity::Valid);
+		assert_eq!(validate("10.2.0.0/17", 64496), RouteOriginValidity::Invalid);
+		assert_eq!(validate("10.1.2.0/24", 64497), RouteOriginValidity::Valid);
+		assert_eq!(validate("10.1.0.0/16", 64496), RouteOriginValidity::Valid);
+		assert_eq!(validate("10.1.2.0/24", 64496), RouteOriginValidity::Invalid);
+		assert_eq!(validate("0.0.0.0/0", 64496), RouteOriginValidity::NotFound);
+		// AS 0 ROAs never match
+		assert_eq!(validate("192.0.2.0/24", 0), RouteOriginValidity::Invalid);
+		assert_eq!(
+			validate("2001:db8:1::/48", 64496),
+			RouteOriginValidity::Valid
+		);
+		assert_eq!(
+			validate("2001:db9::/32", 64496),
+			RouteOriginValidity::NotFound
+		);
+
+		assert_eq!(
+			table.covering(&cidr("10.1.2.0/24")).collect::<Vec<_>>(),
+			[
+				&roa("10.0.0.0/8", 16, 64496),
+				&roa("10.1.0.0/16", 24, 64497)
+			]
+		);
+		assert_eq!(Roa::new(cidr("10.0.0.0/8"), 7, 64496), None);
+		assert_eq!(Roa::new(cidr("10.0.0.0/8"), 33, 64496), None);
+	}
+
+	#[test]
+	fn parse_csv() {
+		let table = RoaTable::parse_csv(
+			"ASN,IP Prefix,Max Length,Trust Anchor,Expires\n\
+			 AS64496,10.0.0.0/8,16,ripe,1700000000\n\
+			 64497,2001:db8::/32,,arin\n\
+			 \n\
+			 as64496,10.0.0.0/8,16,apnic\n",
+		)
+		.unwrap();
+		assert_eq!(
+			table.iter().copied().collect::<Vec<_>>(),
+			[
+				roa("10.0.0.0/8", 16, 64496),
+				roa("2001:db8::/32", 32, 64497)
+			]
+		);
+
+		let error = |input| {
+			let e = RoaTable::parse_csv(input).unwrap_err();
+			(e.entry(), e.kind())
+		};
+		assert_eq!(error("AS1\n"), (1, ErrorKind::InvalidSyntax));
+		assert_eq!(error("ASX,10.0.0.0/8"), (1, ErrorKind::InvalidSyntax));
+		assert_eq!(
+			error("\nAS1,10.0.0.0/8,7"),
+			(2, ErrorKind::InvalidNetworkLength)
+		);
+		assert_eq!(
+			error("AS1,10.0.0.0/8,x"),
+			(1, ErrorKind::InvalidNetworkLength)
+		);
+		assert_eq!(error("AS1,10.0.0.1/8,8"), (1, ErrorKind::InvalidHostPart));
+	}
+
+	#[cfg(feature = "serde_json")]
+	#[test]
+	fn parse_json() {
+		let table = RoaTable::parse_json(
+			r#"{
+				"metadata": { "generated": 1700000000 },
+				"roas": [
+					{ "asn": "AS64496", "prefix": "10.0.0.0/8", "maxLength": 16, "ta": "ripe" },
+					{ "asn": 64497, "prefix": "2001:db8::/32", "ta": "arin" }
+				]
+			}"#,
+		)
+		.unwrap();
+		assert_eq!(
+			table.iter().copied().collect::<Vec<_>>(),
+			[
+				roa("10.0.0.0/8", 16, 64496),
+				roa("2001:db8::/32", 32, 64497)
+			]
+		);
+
+		let error = |input| {
+			let e = RoaTable::parse_json(input).unwrap_err();
+			(e.entry(), e.kind())
+		};
+		assert_eq!(error("{"), (0, ErrorKind::InvalidSyntax));
+		assert_eq!(error("{}"), (0, ErrorKind::InvalidSyntax));
+		assert_eq!(
+			error(r#"{"roas": [{"asn": 1, "prefix": "::/0"}, {"asn": -1, "prefix": "::/0"}]}"#),
+			(2, ErrorKind::InvalidSyntax)
+		);
+		assert_eq!(
+			error(r#"{"roas": [{"asn": 1, "prefix": "::/0", "maxLength": 300}]}"#),
+			(1, ErrorKind::InvalidNetworkLength)
+		);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn load() {
+		let path =
+			std::env::temp_dir().join(alloc::format!("cidr-rpki-{}.csv", std::process::id()));
+		std::fs::write(&path, "AS64496,192.0.2.0/24,24\n").unwrap();
+		let table = RoaTable::load_csv(&path);
+		std::fs::write(&path, "AS64496,192.0.2.0/24,23\n").unwrap();
+		let invalid = RoaTable::load_csv(&path);
+		std::fs::remove_file(&path).unwrap();
+		assert_eq!(table.unwrap().len(), 1);
+		let invalid = invalid.unwrap_err();
+		assert_eq!(invalid.kind(), std::io::ErrorKind::InvalidData);
+		assert!(invalid.get_ref().unwrap().is::<RoaParseError>());
+		assert_eq!(
+			RoaTable::load_csv(&path).unwrap_err().kind(),
+			std::io::ErrorKind::NotFound
+		);
+	}
+}
@@ -0,0 +1,254 @@
+//! [`proptest`](https://crates.io/crates/proptest) strategies
+//!
+//! All network, host and pair types also implement
+//! `proptest::arbitrary::Arbitrary`, so `any::<IpCidr>()` works; the
+//! functions in this module generate values with constraints, e.g. only
+//! certain network lengths or only hosts within a given network.
+//!
+//! All generated values are valid by construction; host parts (and
+//! offsets within networks) shrink towards zero.
+//!
+//! ```
+//! use cidr::{strategies, Cidr, Ipv4Cidr};
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn subnet_is_contained(
+//!         (net, subnet) in strategies::ipv4_cidr(8..=16)
+//!             .prop_flat_map(|net| (Just(net), strategies::subnet_of(net, 24..=32)))
+//!     ) {
+//!         prop_assert!(net.contains(&subnet.first_address()));
+//!     }
+//! }
+//! # subnet_is_contained();
+//! ```
+
+use core::{
+	net::{
+		Ipv4Addr,
+		Ipv6Addr,
+	},
+	ops::RangeInclusive,
+};
+
+use proptest::{
+	arbitrary::{
+		any,
+		Arbitrary,
+	},
+	prop_oneof,
+	strategy::{
+		BoxedStrategy,
+		Just,
+		Strategy,
+	},
+};
+
+use crate::{
+	Address,
+	AnyIpCidr,
+	AnyIpInet,
+	Cidr,
+	Family,
+	Inet,
+	InetPair,
+	IpCidr,
+	IpInet,
+	IpInetPair,
+	Ipv4Cidr,
+	Ipv4Inet,
+	Ipv4InetPair,
+	Ipv6Cidr,
+	Ipv6Inet,
+	Ipv6InetPair,
+};
+
+fn check_lengths(lengths: &RangeInclusive<u8>, min: u8, max: u8) {
+	assert!(
+		min <= *lengths.start() && lengths.start() <= lengths.end() && *lengths.end() <= max,
+		"network lengths {:?} not in {}..={}",
+		lengths,
+		min,
+		max
+	);
+}
+
+/// Hosts with a network length in the given range
+///
+/// Panics if the range is empty or contains lengths above 32.
+pub fn ipv4_inet(lengths: RangeInclusive<u8>) -> impl Strategy<Value = Ipv4Inet> {
+	check_lengths(&lengths, 0, Family::Ipv4.len());
+	(any::<Ipv4Addr>(), lengths)
+		.prop_map(|(address, len)| Ipv4Inet::new(address, len).expect("valid network length"))
+}
+
+/// Hosts with a network length in the given range
+///
+/// Panics if the range is empty or contains lengths above 128.
+pub fn ipv6_inet(lengths: RangeInclusive<u8>) -> impl Strategy<Value = Ipv6Inet> {
+	check_lengths(&lengths, 0, Family::Ipv6.len());
+	(any::<Ipv6Addr>(), lengths)
+		.prop_map(|(address, len)| Ipv6Inet::new(address, len).expect("valid network length"))
+}
+
+/// Networks with a network length in the given range
+///
+/// Panics if the range is empty or contains lengths above 32.
+pub fn ipv4_cidr(lengths: RangeInclusive<u8>) -> impl Strategy<Value = Ipv4Cidr> {
+	ipv4_inet(lengths).prop_map(|inet| inet.network())
+}
+
+/// Networks with a network length in the given range
+///
+/// Panics if the range is empty or contains lengths above 128.
+pub fn ipv6_cidr(lengths: RangeInclusive<u8>) -> impl Strategy<Value = Ipv6Cidr> {
+	ipv6_inet(lengths).prop_map(|inet| inet.network())
+}
+
+/// Pairs of hosts in a network with a network length in the given range
+///
+/// Panics if the range is empty or contains lengths above 32.
+pub fn ipv4_inet_pair(lengths: RangeInclusive<u8>) -> impl Strategy<Value = Ipv4InetPair> {
+	ipv4_cidr(lengths).prop_flat_map(inet_pair_in)
+}
+
+/// Pairs of hosts in a network with a network length in the given range
+///
+/// Panics if the range is empty or contains lengths above 128.
+pub fn ipv6_inet_pair(lengths: RangeInclusive<u8>) -> impl Strategy<Value = Ipv6InetPair> {
+	ipv6_cidr(lengths).prop_flat_map(inet_pair_in)
+}
+
+/// Hosts within the given network
+pub fn inet_in<C: Cidr + 'static>(
+	network: C,
+) -> impl Strategy<Value = <C::Address as Address>::Inet> {
+	any::<u128>().prop_map(move |offset| network.first().wrapping_add(offset))
+}
+
+/// Addresses within the given network
+pub fn address_in<C: Cidr + 'static>(network: C) -> impl Strategy<Value = C::Address> {
+	inet_in(network).prop_map(|inet| inet.address())
+}
+
+/// Pairs of hosts within the given network
+pub fn inet_pair_in<C: Cidr + 'static>(
+	network: C,
+) -> impl Strategy<Value = <C::Address as Address>::InetPair> {
+	(inet_in(network), inet_in(network)).prop_map(|(first, second)| {
+		<C::Address as Address>::InetPair::new(first, second).expect("same network")
+	})
+}
+
+/// Subnets of the given network with a network length in the given range
+///
+/// Panics if the range is empty or contains lengths shorter than the
+/// network length of `network` or longer than the address length.
+pub fn subnet_of<C: Cidr + 'static>(
+	network: C,
+	lengths: RangeInclusive<u8>,
+) -> impl Strategy<Value = C> {
+	check_lengths(&lengths, network.network_length(), network.family().len());
+	(inet_in(network), lengths).prop_map(|(inet, len)| {
+		<C::Address as Address>::Inet::new(inet.address(), len)
+			.expect("valid network length")
+			.network()
+	})
+}
+
+macro_rules! impl_arbitrary_for {
+	($t:ident => $strategy:expr) => {
+		impl Arbitrary for $t {
+			type Parameters = ();
+			type Strategy = BoxedStrategy<Self>;
+
+			fn arbitrary_with(_args: ()) -> Self::Strategy {
+				$strategy.boxed()
+			}
+		}
+	};
+}
+
+impl_arbitrary_for! {Ipv4Inet => ipv4_inet(0..=32)}
+impl_arbitrary_for! {Ipv6Inet => ipv6_inet(0..=128)}
+impl_arbitrary_for! {Ipv4Cidr => ipv4_cidr(0..=32)}
+impl_arbitrary_for! {Ipv6Cidr => ipv6_cidr(0..=128)}
+impl_arbitrary_for! {Ipv4InetPair => ipv4_inet_pair(0..=32)}
+impl_arbitrary_for! {Ipv6InetPair => ipv6_inet_pair(0..=128)}
+impl_arbitrary_for! {IpInet => prop_oneof![
+	any::<Ipv4Inet>().prop_map(IpInet::V4),
+	any::<Ipv6Inet>().prop_map(IpInet::V6),
+]}
+impl_arbitrary_for! {IpCidr => prop_oneof![
+	any::<Ipv4Cidr>().prop_map(IpCidr::V4),
+	any::<Ipv6Cidr>().prop_map(IpCidr::V6),
+]}
+impl_arbitrary_for! {IpInetPair => prop_oneof![
+	any::<Ipv4InetPair>().prop_map(IpInetPair::V4),
+	any::<Ipv6InetPair>().prop_map(IpInetPair::V6),
+]}
+impl_arbitrary_for! {AnyIpCidr => prop_oneof![
+	Just(AnyIpCidr::Any),
+	any::<Ipv4Cidr>().prop_map(AnyIpCidr::V4),
+	any::<Ipv6Cidr>().prop_map(AnyIpCidr::V6),
+]}
+impl_arbitrary_for! {AnyIpInet => prop_oneof![
+	Just(AnyIpInet::Any),
+	any::<Ipv4Inet>().prop_map(AnyIpInet::V4),
+	any::<Ipv6Inet>().prop_map(AnyIpInet::V6),
+]}
+
+#[cfg(test)]
+mod tests {
+	use proptest::prelude::*;
+
+	use super::*;
+
+	proptest! {
+		#[test]
+		fn parse_display_roundtrip(
+			cidr in any::<AnyIpCidr>(),
+			inet in any::<IpInet>(),
+			any_inet in any::<AnyIpInet>(),
+		) {
+			prop_assert_eq!(cidr.to_string().parse::<AnyIpCidr>(), Ok(cidr));
+			prop_assert_eq!(inet.to_string().parse::<IpInet>(), Ok(inet));
+			prop_assert_eq!(any_inet.to_string().parse::<AnyIpInet>(), Ok(any_inet));
+		}
+
+		#[test]
+		fn constrained_lengths(cidr in ipv4_cidr(16..=24), inet in ipv6_inet(48..=64)) {
+			prop_assert!((16..=24).contains(&cidr.network_length()));
+			prop_assert!((48..=64).contains(&inet.network_length()));
+		}
+
+		#[test]
+		fn within_network(
+			(net, inet, subnet) in any::<IpCidr>().prop_flat_map(|net| {
+				let len = net.family().len();
+				(Just(net), inet_in(net), subnet_of(net, net.network_length()..=len))
+			}),
+		) {
+			prop_assert_eq!(inet.network(), net);
+			prop_assert!(net.contains(&subnet.first_address()));
+			prop_assert!(net.contains(&subnet.last_address()));
+		}
+
+		#[test]
+		fn pairs(pair in any::<IpInetPair>()) {
+			prop_assert_eq!(pair.first().network(), pair.second().network());
+		}
+	}
+
+	#[test]
+	#[should_panic]
+	fn invalid_lengths() {
+		let _ = ipv4_cidr(16..=33);
+	}
+
+	#[test]
+	#[should_panic]
+	fn subnet_too_short() {
+		let _ = subnet_of("10.0.0.0/8".parse::<Ipv4Cidr>().unwrap(), 4..=16);
+	}
+}
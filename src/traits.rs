@@ -8,8 +8,10 @@ use crate::{
 		PrivCidr,
 		PrivInet,
 		PrivInetPair,
+		PrivNetworkLike,
 		PrivUnspecAddress,
 	},
+	AnyIpCidr,
 	Family,
 	InetIterator,
 };
@@ -19,7 +21,10 @@ use core::{
 		Display,
 	},
 	hash::Hash,
-	net::AddrParseError,
+	net::{
+		AddrParseError,
+		IpAddr,
+	},
 	str::FromStr,
 };
 
@@ -86,6 +91,16 @@ pub trait Cidr:
 	/// addresses).
 	fn iter(&self) -> InetIterator<Self::Address>;
 
+	/// Iterate over the addresses usable for hosts
+	///
+	/// Excludes the IPv4 network and broadcast addresses and the IPv6
+	/// subnet-router anycast address (apart from point-to-point and host
+	/// networks); see [`Ipv4Cidr::hosts`] and [`Ipv6Cidr::hosts`].
+	///
+	/// [`Ipv4Cidr::hosts`]: crate::Ipv4Cidr::hosts
+	/// [`Ipv6Cidr::hosts`]: crate::Ipv6Cidr::hosts
+	fn hosts(&self) -> InetIterator<Self::Address>;
+
 	/// first address in the network as plain address
 	fn first_address(&self) -> Self::Address;
 	/// first address in the network
@@ -111,6 +126,69 @@ pub trait Cidr:
 
 	/// check whether an address is contained in the network
 	fn contains(&self, addr: &Self::Address) -> bool;
+
+	/// address with the given index in the network (index `0` is the
+	/// first address) as plain address
+	///
+	/// Returns `None` if the index is too large for the network.
+	fn nth_address(&self, index: u128) -> Option<Self::Address>;
+
+	/// address with the given index in the network (index `0` is the
+	/// first address)
+	///
+	/// Returns `None` if the index is too large for the network.
+	fn nth(&self, index: u128) -> Option<<Self::Address as Address>::Inet>;
+
+	/// address with the given index counted from the end of the network
+	/// (index `0` is the last address) as plain address
+	///
+	/// Returns `None` if the index is too large for the network.
+	fn nth_address_from_end(&self, index: u128) -> Option<Self::Address>;
+
+	/// address with the given index counted from the end of the network
+	/// (index `0` is the last address)
+	///
+	/// Returns `None` if the index is too large for the network.
+	fn nth_from_end(&self, index: u128) -> Option<<Self::Address as Address>::Inet>;
+
+	/// index of the address in the network (the first address has index
+	/// `0`)
+	///
+	/// Returns `None` if the address is not contained in the network.
+	fn index_of(&self, addr: &Self::Address) -> Option<u128>;
+}
+
+/// Types implementing [`NetworkLike`] represent either an IP network or
+/// "any" (all IPv4 and IPv6 addresses).
+///
+/// Implemented for all [`Cidr`] types and [`AnyIpCidr`]; allows generic
+/// code (e.g. access rules) to accept a specific network as well as
+/// "any".
+pub trait NetworkLike:
+	Copy + Debug + Display + FromStr<Err = NetworkParseError> + Ord + Hash + PrivNetworkLike
+{
+	/// Convert to [`AnyIpCidr`]
+	fn to_any_ip_cidr(&self) -> AnyIpCidr;
+
+	/// Whether representing any address
+	fn is_any(&self) -> bool {
+		self.to_any_ip_cidr().is_any()
+	}
+
+	/// check whether an address is contained in the network
+	fn contains_address(&self, addr: &IpAddr) -> bool {
+		self.to_any_ip_cidr().contains(addr)
+	}
+
+	/// check whether all addresses of `other` are contained in the
+	/// network
+	///
+	/// Networks of different families never contain each other; "any"
+	/// contains all networks (but is only contained in "any").
+	fn contains_network<N: NetworkLike>(&self, other: &N) -> bool {
+		self.to_any_ip_cidr()
+			._contains_network(&other.to_any_ip_cidr())
+	}
 }
 
 /// Types implementing Inet represent IP hosts within networks.
@@ -169,6 +247,42 @@ pub trait Inet:
 	/// Returned boolean indicates whether an overflow occured.
 	fn overflowing_sub(self, step: u128) -> (Self, bool);
 
+	/// Find the nth host after the current one in the current network
+	///
+	/// Returns `None` if the result would leave the network.
+	fn checked_add(self, step: u128) -> Option<Self>;
+
+	/// Find the nth host before the current one in the current network
+	///
+	/// Returns `None` if the result would leave the network.
+	fn checked_sub(self, step: u128) -> Option<Self>;
+
+	/// Find the nth host after the current one in the current network
+	///
+	/// Returns the last address in the network if the result would leave
+	/// the network.
+	fn saturating_add(self, step: u128) -> Self;
+
+	/// Find the nth host before the current one in the current network
+	///
+	/// Returns the first address in the network if the result would leave
+	/// the network.
+	fn saturating_sub(self, step: u128) -> Self;
+
+	/// Find the nth host after the current one in the current network
+	///
+	/// Wraps around within the network (only the host part changes).
+	fn wrapping_add(self, step: u128) -> Self;
+
+	/// Find the nth host before the current one in the current network
+	///
+	/// Wraps around within the network (only the host part changes).
+	fn wrapping_sub(self, step: u128) -> Self;
+
+	/// Index of the host in the network (i.e. the host part of the address
+	/// as number)
+	fn offset_in_network(&self) -> u128;
+
 	/// network (i.e. drops the host information)
 	fn network(&self) -> <Self::Address as Address>::Cidr;
 